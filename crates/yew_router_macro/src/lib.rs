@@ -5,7 +5,17 @@ mod switch;
 
 
 /// Implements `Switch` trait based on attributes present on the struct or enum variants.
-#[proc_macro_derive(Switch, attributes(to, lit, cap, rest, query, frag))]
+///
+/// Enum variants are tried in the order they are declared in,
+/// unless the enum has the `#[most_specific]` attribute,
+/// in which case the variant with the most specific matcher is tried first.
+///
+/// A `#[title = "Profile {id}"]` attribute on the struct or a variant provides its title,
+/// formatting the fields it names into it.
+#[proc_macro_derive(
+    Switch,
+    attributes(to, lit, cap, rest, query, frag, most_specific, title)
+)]
 pub fn switch(tokens: TokenStream) -> TokenStream {
     crate::switch::switch_impl(tokens)
 }
@@ -4,23 +4,27 @@ use proc_macro::TokenStream;
 use syn::{parse_macro_input, Fields};
 //use syn::punctuated::IntoIter;
 use crate::switch::enum_impl::generate_enum_impl;
-use crate::switch::shadow::ShadowMatcherToken;
+use crate::switch::shadow::{ShadowCaptureVariant, ShadowMatcherToken};
 use crate::switch::struct_impl::generate_struct_impl;
 use syn::export::TokenStream2;
-use syn::{Data, DeriveInput, Ident, Variant};
+use syn::{Data, DeriveInput, Ident, Type, Variant};
 
 mod attribute;
 mod enum_impl;
 mod shadow;
 mod struct_impl;
+mod title;
 
 use self::attribute::AttrToken;
+use self::title::title_attribute;
 
 /// Holds data that is required to derive Switch for a struct or a single enum variant.
 pub struct SwitchItem {
     pub matcher: Vec<ShadowMatcherToken>,
     pub ident: Ident,
     pub fields: Fields,
+    /// The template provided by the `#[title]` attribute.
+    pub title: Option<String>,
 }
 
 pub fn switch_impl(input: TokenStream) -> TokenStream {
@@ -30,6 +34,7 @@ pub fn switch_impl(input: TokenStream) -> TokenStream {
 
     match input.data {
         Data::Struct(ds) => {
+            let title = title_attribute(&input.attrs);
             let mut encountered_query = false;
             let matcher = AttrToken::convert_attributes_to_tokens(input.attrs)
                 .into_iter()
@@ -41,11 +46,17 @@ pub fn switch_impl(input: TokenStream) -> TokenStream {
                 matcher,
                 ident,
                 fields: ds.fields,
+                title,
             };
             generate_struct_impl(switch_item)
         }
         Data::Enum(de) => {
+            let most_specific = input
+                .attrs
+                .iter()
+                .any(|attr| attr.path.is_ident("most_specific"));
             let switch_variants = de.variants.into_iter().map(|variant: Variant| {
+                let title = title_attribute(&variant.attrs);
                 let mut encountered_query = false;
                 let matcher = AttrToken::convert_attributes_to_tokens(variant.attrs)
                     .into_iter()
@@ -57,9 +68,10 @@ pub fn switch_impl(input: TokenStream) -> TokenStream {
                     matcher,
                     ident: variant.ident,
                     fields: variant.fields,
+                    title,
                 }
             });
-            generate_enum_impl(ident, switch_variants)
+            generate_enum_impl(ident, switch_variants, most_specific)
         }
         Data::Union(_du) => panic!("Deriving FromCaptures not supported for Unions."),
     }
@@ -86,6 +98,7 @@ fn build_matcher_from_tokens(tokens: Vec<ShadowMatcherToken>) -> TokenStream2 {
             strict: true, // Don't add optional sections
             complete: false, // Allow incomplete matches. // TODO investigate if this is necessary here.
             case_insensitive: true,
+            decode_captures: true,
         };
         let matcher = ::yew_router::matcher::RouteMatcher {
             tokens : vec![#(#tokens),*],
@@ -93,3 +106,115 @@ fn build_matcher_from_tokens(tokens: Vec<ShadowMatcherToken>) -> TokenStream2 {
         };
     }
 }
+
+/// Generates code that explains how the route was matched against an item's matcher,
+/// and pushes it to a `reports` vector.
+///
+/// `build_from_captures` is the code that returns the item from the enclosing function
+/// if it can be produced from the route.
+fn build_explanation(
+    name: &str,
+    item_ty: &Ident,
+    matcher: &TokenStream2,
+    build_from_captures: &TokenStream2,
+) -> TokenStream2 {
+    quote::quote! {
+        {
+            #matcher
+            let report = matcher.explain(&route.to_string());
+            let produce = || -> Option<#item_ty> {
+                #build_from_captures
+                None
+            };
+            reports.push(::yew_router::SwitchReport {
+                variant: #name,
+                report,
+                produced: produce().is_some(),
+            });
+        }
+    }
+}
+
+/// The names of the captures that the matcher will collect, in the order they are collected,
+/// along with whether each of them captures many sections.
+///
+/// Unnamed fields are populated from these captures by their position.
+fn named_captures(tokens: &[ShadowMatcherToken]) -> Vec<(String, bool)> {
+    let mut captures = Vec::new();
+    for token in tokens {
+        match token {
+            ShadowMatcherToken::Exact(_) => {}
+            ShadowMatcherToken::Capture(capture) => match &capture.capture_variant {
+                ShadowCaptureVariant::Named(name)
+                | ShadowCaptureVariant::NumberedNamed { name, .. } => {
+                    captures.push((name.clone(), false))
+                }
+                ShadowCaptureVariant::ManyNamed(name) => captures.push((name.clone(), true)),
+                ShadowCaptureVariant::Unnamed
+                | ShadowCaptureVariant::ManyUnnamed
+                | ShadowCaptureVariant::NumberedUnnamed { .. } => {}
+            },
+            ShadowMatcherToken::Optional(optional) => captures.extend(named_captures(optional)),
+        }
+    }
+    captures
+}
+
+/// Generates code that describes an item's matcher, and pushes it to a `descriptions` vector.
+///
+/// Fields that are populated by captures spanning many sections, like `{*:rest}`,
+/// are described as the children of the item.
+///
+/// `captures` are the `named_captures` of the item's matcher.
+fn build_description(
+    name: &str,
+    captures: &[(String, bool)],
+    fields: &Fields,
+    matcher: &TokenStream2,
+) -> TokenStream2 {
+    let is_many = |key: &str| captures.iter().any(|(name, many)| *many && name == key);
+    let nested: Vec<(String, &Type)> = match fields {
+        Fields::Named(named_fields) => named_fields
+            .named
+            .iter()
+            .filter_map(|field| field.ident.as_ref().map(|ident| (ident.to_string(), &field.ty)))
+            .filter(|(key, _)| is_many(key))
+            .collect(),
+        Fields::Unnamed(unnamed_fields) => unnamed_fields
+            .unnamed
+            .iter()
+            .zip(captures.iter())
+            .filter(|(_, (_, many))| *many)
+            .map(|(field, (key, _))| (key.clone(), &field.ty))
+            .collect(),
+        Fields::Unit => vec![],
+    };
+    let (keys, types): (Vec<String>, Vec<&Type>) = nested.into_iter().unzip();
+
+    quote::quote! {
+        {
+            #matcher
+            let mut capture_names: Vec<String> = matcher
+                .capture_names()
+                .into_iter()
+                .map(String::from)
+                .collect();
+            capture_names.sort();
+            let nested: Vec<(&str, Vec<::yew_router::SwitchDescription>)> = vec![
+                #((#keys, <#types as ::yew_router::Switch>::describe())),*
+            ];
+            let (rest, children) = nested
+                .into_iter()
+                .find(|(_, children)| !children.is_empty())
+                .map(|(rest, children)| (Some(rest.to_string()), children))
+                .unwrap_or_default();
+            descriptions.push(::yew_router::SwitchDescription {
+                variant: #name,
+                matcher,
+                capture_names,
+                rest,
+                children,
+            });
+        }
+    }
+}
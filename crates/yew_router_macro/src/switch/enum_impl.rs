@@ -1,12 +1,19 @@
+use crate::switch::title::build_title_arm;
 use crate::switch::SwitchItem;
 use proc_macro::TokenStream;
 use quote::quote;
+use std::cmp::Reverse;
 use syn::export::TokenStream2;
 use syn::{Field, Fields, Ident, Type};
+use yew_router_route_parser::{MatcherToken, Specificity};
 
+/// If `most_specific` is set, the variants are tried in order of the specificity of their matchers,
+/// instead of the order they were declared in.
+/// The order is determined here, so the generated code tries the variants in that order directly.
 pub fn generate_enum_impl(
     enum_ident: Ident,
     switch_variants: impl Iterator<Item = SwitchItem>,
+    most_specific: bool,
 ) -> TokenStream {
     /// Once the 'captures' exists, attempt to populate the fields from the list of captures.
     fn build_variant_from_captures(
@@ -96,24 +103,71 @@ pub fn generate_enum_impl(
         }
     }
 
-    let variant_matchers: Vec<TokenStream2> = switch_variants
-        .into_iter()
-        .map(|sv| {
-            let SwitchItem {
-                matcher,
-                ident,
-                fields,
-            } = sv;
-            let build_from_captures = build_variant_from_captures(&enum_ident, ident, fields);
-            let matcher = super::build_matcher_from_tokens(matcher);
+    // The matching, explaining and describing code of each variant, with its specificity.
+    let mut variants: Vec<(Specificity, TokenStream2, TokenStream2, TokenStream2)> = Vec::new();
+    let mut title_arms = Vec::new();
+    let mut has_titles = false;
+    for sv in switch_variants {
+        let SwitchItem {
+            matcher,
+            ident,
+            fields,
+            title,
+        } = sv;
+        let variant_name = ident.to_string();
+        has_titles |= title.is_some();
+        title_arms.push(build_title_arm(
+            &variant_name,
+            quote! { #enum_ident::#ident },
+            &fields,
+            &title,
+        ));
+        let captures = super::named_captures(&matcher);
+        let tokens: Vec<MatcherToken> = matcher.iter().map(MatcherToken::from).collect();
+        let specificity = Specificity::of(&tokens);
+        let matcher = super::build_matcher_from_tokens(matcher);
+        let description = super::build_description(&variant_name, &captures, &fields, &matcher);
+        let build_from_captures = build_variant_from_captures(&enum_ident, ident, fields);
+        let build_from_captures = quote! {
+            let state = &route.state; // TODO State gets cloned a bunch here. Some refactorings should aim to remove this.
+            #build_from_captures
+        };
 
-            quote! {
-                #matcher
-                let state = &route.state; // TODO State gets cloned a bunch here. Some refactorings should aim to remove this.
-                #build_from_captures
+        let explanation = super::build_explanation(
+            &variant_name,
+            &enum_ident,
+            &matcher,
+            &build_from_captures,
+        );
+        let variant_matcher = quote! {
+            #matcher
+            #build_from_captures
+        };
+        variants.push((specificity, variant_matcher, explanation, description));
+    }
+
+    if most_specific {
+        // The variants are tried from the most specific to the least specific.
+        // Sorting is stable, so equally specific variants are tried in declaration order.
+        variants.sort_by_key(|(specificity, _, _, _)| Reverse(*specificity));
+    }
+    let variant_matchers = variants.iter().map(|(_, matcher, _, _)| matcher);
+    let variant_explanations = variants.iter().map(|(_, _, explanation, _)| explanation);
+    let variant_descriptions = variants.iter().map(|(_, _, _, description)| description);
+
+    // Variants don't have titles by default, so there is no need to override it.
+    let title_fn = if has_titles {
+        quote! {
+            #[allow(unused_variables)]
+            fn title(&self) -> Option<String> {
+                match self {
+                    #(#title_arms)*
+                }
             }
-        })
-        .collect::<Vec<_>>();
+        }
+    } else {
+        quote! {}
+    };
 
     let token_stream = quote! {
         impl ::yew_router::Switch for #enum_ident {
@@ -122,6 +176,20 @@ pub fn generate_enum_impl(
 
                 return None
             }
+
+            fn explain<T: yew_router::route::RouteState>(route: ::yew_router::route::Route<T>) -> Vec<::yew_router::SwitchReport> {
+                let mut reports = Vec::new();
+                #(#variant_explanations)*
+                reports
+            }
+
+            fn describe() -> Vec<::yew_router::SwitchDescription> {
+                let mut descriptions = Vec::new();
+                #(#variant_descriptions)*
+                descriptions
+            }
+
+            #title_fn
         }
     };
     TokenStream::from(token_stream)
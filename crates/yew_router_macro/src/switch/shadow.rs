@@ -109,6 +109,18 @@ impl From<MatcherToken> for ShadowMatcherToken {
     }
 }
 
+impl From<&ShadowMatcherToken> for MatcherToken {
+    fn from(sot: &ShadowMatcherToken) -> Self {
+        use MatcherToken as MT;
+        use ShadowMatcherToken as SOT;
+        match sot {
+            SOT::Exact(s) => MT::Exact(s.clone()),
+            SOT::Capture(capture) => MT::Capture(capture.into()),
+            SOT::Optional(optional) => MT::Optional(optional.iter().map(MT::from).collect()),
+        }
+    }
+}
+
 impl From<CaptureVariant> for ShadowCaptureVariant {
     fn from(cv: CaptureVariant) -> Self {
         use CaptureVariant as CV;
@@ -126,6 +138,26 @@ impl From<CaptureVariant> for ShadowCaptureVariant {
     }
 }
 
+impl From<&ShadowCaptureVariant> for CaptureVariant {
+    fn from(scv: &ShadowCaptureVariant) -> Self {
+        use CaptureVariant as CV;
+        use ShadowCaptureVariant as SCV;
+        match scv {
+            SCV::Unnamed => CV::Unnamed,
+            SCV::ManyUnnamed => CV::ManyUnnamed,
+            SCV::NumberedUnnamed { sections } => CV::NumberedUnnamed {
+                sections: *sections,
+            },
+            SCV::Named(name) => CV::Named(name.clone()),
+            SCV::ManyNamed(name) => CV::ManyNamed(name.clone()),
+            SCV::NumberedNamed { sections, name } => CV::NumberedNamed {
+                sections: *sections,
+                name: name.clone(),
+            },
+        }
+    }
+}
+
 impl From<Capture> for ShadowCapture {
     fn from(c: Capture) -> Self {
         ShadowCapture {
@@ -134,3 +166,12 @@ impl From<Capture> for ShadowCapture {
         }
     }
 }
+
+impl From<&ShadowCapture> for Capture {
+    fn from(sc: &ShadowCapture) -> Self {
+        Capture {
+            capture_variant: (&sc.capture_variant).into(),
+            allowed_captures: sc.allowed_captures.clone(),
+        }
+    }
+}
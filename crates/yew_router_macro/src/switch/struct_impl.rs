@@ -1,3 +1,4 @@
+use crate::switch::title::build_title_arm;
 use crate::switch::SwitchItem;
 use proc_macro2::Ident;
 use quote::quote;
@@ -9,14 +10,37 @@ pub fn generate_struct_impl(item: SwitchItem) -> TokenStream {
         matcher,
         ident,
         fields,
+        title,
     } = item;
-    let build_from_captures = build_variant_from_captures(&ident, fields);
+    let title_fn = if title.is_some() {
+        let title_arm = build_title_arm(&ident.to_string(), quote! { #ident }, &fields, &title);
+        quote! {
+            #[allow(unused_variables)]
+            fn title(&self) -> Option<String> {
+                match self {
+                    #title_arm
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let captures = super::named_captures(&matcher);
     let matcher = super::build_matcher_from_tokens(matcher);
+    let description =
+        super::build_description(&ident.to_string(), &captures, &fields, &matcher);
+    let build_from_captures = build_variant_from_captures(&ident, fields);
+    let build_from_captures = quote! {
+        let state = route.state.clone(); // TODO State gets cloned a bunch here. Some refactorings should aim to remove this.
+        #build_from_captures
+    };
+
+    let explanation =
+        super::build_explanation(&ident.to_string(), &ident, &matcher, &build_from_captures);
 
     let item_matcher = quote! {
 
         #matcher
-        let state = route.state.clone(); // TODO State gets cloned a bunch here. Some refactorings should aim to remove this.
         #build_from_captures
     };
 
@@ -27,6 +51,20 @@ pub fn generate_struct_impl(item: SwitchItem) -> TokenStream {
 
                 return None
             }
+
+            fn explain<T: yew_router::route::RouteState>(route: ::yew_router::route::Route<T>) -> Vec<::yew_router::SwitchReport> {
+                let mut reports = Vec::new();
+                #explanation
+                reports
+            }
+
+            fn describe() -> Vec<::yew_router::SwitchDescription> {
+                let mut descriptions = Vec::new();
+                #description
+                descriptions
+            }
+
+            #title_fn
         }
     };
     TokenStream::from(token_stream)
@@ -0,0 +1,134 @@
+use proc_macro2::{Ident, Span};
+use quote::quote;
+use syn::export::TokenStream2;
+use syn::{Attribute, Fields, Lit, Meta};
+
+/// Gets the template provided by a `#[title = "..."]` attribute.
+pub fn title_attribute(attributes: &[Attribute]) -> Option<String> {
+    attributes
+        .iter()
+        .filter_map(|attr: &Attribute| attr.parse_meta().ok())
+        .filter_map(|meta: Meta| match meta {
+            Meta::NameValue(mnv) if mnv.path.is_ident("title") => match mnv.lit {
+                Lit::Str(s) => Some(s.value()),
+                _ => panic!("Value provided after `title` must be a String"),
+            },
+            _ => None,
+        })
+        .next()
+}
+
+/// Converts a title template, like `Profile {id}`, into a format string and the names of the
+/// fields that are formatted into it, in order.
+///
+/// Fields are referred to by name, or by position for tuple variants, like `{0}`.
+/// Format specifiers, like `{id:>4}`, are kept.
+fn parse_title_template(template: &str) -> (String, Vec<String>) {
+    let mut format = String::new();
+    let mut names = Vec::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                format.push_str("{{");
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                format.push_str("}}");
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => panic!("Unclosed `{{` in title: '{}'", template),
+                    }
+                }
+                let mut parts = placeholder.splitn(2, ':');
+                let name = parts.next().unwrap_or_default().trim().to_string();
+                if name.is_empty() {
+                    panic!("Every `{{}}` in a title must name a field: '{}'", template)
+                }
+                names.push(name);
+                format.push('{');
+                if let Some(spec) = parts.next() {
+                    format.push(':');
+                    format.push_str(spec);
+                }
+                format.push('}');
+            }
+            '}' => panic!("Unmatched `}}` in title: '{}'", template),
+            c => format.push(c),
+        }
+    }
+    (format, names)
+}
+
+/// The identifier that a field is bound to when matching against the item.
+fn binding(fields: &Fields, name: &str) -> Option<Ident> {
+    match fields {
+        Fields::Named(named_fields) => named_fields
+            .named
+            .iter()
+            .filter_map(|field| field.ident.clone())
+            .find(|ident| ident == name),
+        Fields::Unnamed(unnamed_fields) => name
+            .parse::<usize>()
+            .ok()
+            .filter(|index| *index < unnamed_fields.unnamed.len())
+            .map(|index| Ident::new(&format!("field_{}", index), Span::call_site())),
+        Fields::Unit => None,
+    }
+}
+
+/// Generates a pattern that binds every field of the item at the path.
+fn binding_pattern(path: &TokenStream2, fields: &Fields) -> TokenStream2 {
+    match fields {
+        Fields::Named(named_fields) => {
+            let idents = named_fields
+                .named
+                .iter()
+                .filter_map(|field| field.ident.as_ref());
+            quote! { #path { #(#idents),* } }
+        }
+        Fields::Unnamed(unnamed_fields) => {
+            let idents = (0..unnamed_fields.unnamed.len())
+                .map(|index| Ident::new(&format!("field_{}", index), Span::call_site()));
+            quote! { #path ( #(#idents),* ) }
+        }
+        Fields::Unit => quote! { #path },
+    }
+}
+
+/// Generates a match arm that produces the title of the item at the path.
+///
+/// `name` is the name of the item, used in error messages.
+pub fn build_title_arm(
+    name: &str,
+    path: TokenStream2,
+    fields: &Fields,
+    title: &Option<String>,
+) -> TokenStream2 {
+    match title {
+        Some(template) => {
+            let (format, names) = parse_title_template(template);
+            let args = names.iter().map(|field_name| {
+                binding(fields, field_name).unwrap_or_else(|| {
+                    panic!(
+                        "The title of `{}` refers to `{}`, which isn't one of its fields",
+                        name, field_name
+                    )
+                })
+            });
+            let pattern = binding_pattern(&path, fields);
+            quote! {
+                #pattern => Some(format!(#format, #(#args),*)),
+            }
+        }
+        None => quote! {
+            #path { .. } => None,
+        },
+    }
+}
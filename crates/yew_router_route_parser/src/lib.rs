@@ -12,15 +12,21 @@
 )]
 
 pub mod parser;
+mod printer;
+mod specificity;
 mod token_optimizer;
+mod validation;
 
 pub use parser::{Capture, CaptureVariant};
+pub use printer::{print_matcher_tokens, print_route_parser_tokens};
+pub use specificity::Specificity;
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 pub use token_optimizer::{
     next_delimiters, optimize_tokens, parse_str_and_optimize_tokens, MatcherToken,
 };
+pub use validation::{validate_matcher_tokens, InvalidMatcherTokens};
 
 /// Captures contain keys corresponding to named match sections,
 /// and values containing the content captured by those sections.
@@ -3,11 +3,11 @@ use crate::parser::CaptureOrExact;
 use crate::parser::RouteParserToken;
 use crate::parser::{Capture, CaptureVariant};
 use nom::branch::alt;
-use nom::bytes::complete::{is_not, tag, take};
-use nom::character::complete::char;
+use nom::bytes::complete::{escaped_transform, is_not, tag, take};
+use nom::character::complete::{char, one_of};
 use nom::character::complete::digit1;
 use nom::character::is_digit;
-use nom::combinator::{map, opt, peek};
+use nom::combinator::{map, opt, peek, verify};
 use nom::error::ParseError;
 use nom::error::{context, ErrorKind, VerboseError};
 use nom::multi::separated_list;
@@ -31,19 +31,39 @@ pub fn valid_ident_characters(i: &str) -> IResult<&str, &str, VerboseError<&str>
     })(i)
 }
 
+/// Characters that have meaning within a matcher string, but can be matched literally
+/// if they are preceded by a `\`.
+pub const ESCAPABLE_CHARACTERS: &str = "()|[]{}=&?# \\";
+
 /// A more permissive set of characters than those specified in `valid_ident_characters that the route string will need to match exactly.
 pub fn valid_exact_match_characters(i: &str) -> IResult<&str, &str, VerboseError<&str>> {
-    const INVALID_CHARACTERS: &str = " /?&#=\t\n()|[]{}";
+    const INVALID_CHARACTERS: &str = " /?&#=\t\n()|[]{}\\";
     context("valid exact match", |i: &str| is_not(INVALID_CHARACTERS)(i))(i)
 }
 
+/// Exact match characters, which may contain escaped characters.
+///
+/// Characters in `ESCAPABLE_CHARACTERS` can appear in the matched text if they are preceded by a `\`.
+/// The returned string has the escaping backslashes removed.
+pub fn escaped_exact_match_characters(i: &str) -> IResult<&str, String, VerboseError<&str>> {
+    context(
+        "escaped exact match",
+        verify(
+            escaped_transform(
+                valid_exact_match_characters,
+                '\\',
+                one_of(ESCAPABLE_CHARACTERS),
+            ),
+            |s: &str| !s.is_empty(),
+        ),
+    )(i)
+}
+
 /// Captures groups of characters that will need to be matched exactly later.
 pub fn match_exact(i: &str) -> IResult<&str, RouteParserToken, VerboseError<&str>> {
     context(
         "match",
-        map(valid_exact_match_characters, |ident| {
-            RouteParserToken::Exact(ident.to_string())
-        }),
+        map(escaped_exact_match_characters, RouteParserToken::Exact),
     )(i)
 }
 
@@ -90,10 +110,7 @@ pub fn capture(i: &str) -> IResult<&str, RouteParserToken, VerboseError<&str>> {
         }),
     ));
 
-    let allowed_matches = map(
-        separated_list(char('|'), valid_exact_match_characters), // TODO this character set may be too limiting (you may want / to appear in the matched section).
-        |x: Vec<&str>| x.into_iter().map(String::from).collect::<Vec<_>>(),
-    );
+    let allowed_matches = separated_list(char('|'), escaped_exact_match_characters); // TODO this character set may be too limiting (you may want / to appear in the matched section).
     let allowed_matches = delimited(char('('), allowed_matches, char(')'));
 
     // Allow capturing the variant, and optionally a list of strings in the form of (string|string|string|...)
@@ -240,4 +257,47 @@ mod test {
     fn can_specify_exact_match_option() {
         capture("{(lorem|ipsum)}").expect("Should complete");
     }
+
+    #[test]
+    fn escaped_exact_match() {
+        let exact = match_exact(r"report\(1\).pdf").expect("Should match");
+        assert_eq!(
+            exact,
+            ("", RouteParserToken::Exact("report(1).pdf".to_string()))
+        );
+    }
+
+    #[test]
+    fn escaped_exact_match_stops_at_unescaped_reserved_character() {
+        let exact = match_exact(r"a\=b=c").expect("Should match");
+        assert_eq!(exact, ("=c", RouteParserToken::Exact("a=b".to_string())));
+    }
+
+    #[test]
+    fn escaped_backslash() {
+        let exact = match_exact(r"a\\b").expect("Should match");
+        assert_eq!(exact, ("", RouteParserToken::Exact(r"a\b".to_string())));
+    }
+
+    #[test]
+    fn only_reserved_characters_can_be_escaped() {
+        match_exact(r"\a").expect_err("Should not match");
+    }
+
+    #[test]
+    fn dangling_escape_is_rejected() {
+        match_exact("lorem\\").expect_err("Should not match");
+    }
+
+    #[test]
+    fn escaped_characters_in_allowed_captures() {
+        let cap = capture(r"{(a\|b|c)}").expect("Should match").1;
+        assert_eq!(
+            cap,
+            RouteParserToken::Capture(Capture {
+                capture_variant: CaptureVariant::Unnamed,
+                allowed_captures: Some(vec!["a|b".to_string(), "c".to_string()])
+            })
+        );
+    }
 }
@@ -1,5 +1,5 @@
 //! Error handling.
-use crate::parser::core::valid_exact_match_characters;
+use crate::parser::core::{valid_exact_match_characters, ESCAPABLE_CHARACTERS};
 use crate::parser::util::skip_until;
 use core::fmt::Write;
 use nom::branch::alt;
@@ -20,6 +20,7 @@ const SECONDARY_QUERIES_USE_AND: &str =
 const UNCLOSED_OPTIONAL: &str = "There are more open parenthesis than close parenthesis. There must be the same number of open parenthesis as close parenthesis.";
 const TOO_MANY_OPTIONAL_CLOSES: &str = "There are more close parenthesis than open parenthesis. There must be the same number of open parenthesis as close parenthesis.";
 const DANGLING_QUERY: &str = "Dangling query. Queries exist in the form '<?|&><exact>=<query|exact>'. The '=<query|exact>' section was left off here.";
+const INVALID_ESCAPE: &str = "Only the following characters can be escaped with a '\\': '(', ')', '|', '[', ']', '{', '}', '=', '&', '?', '#', ' ', and '\\'.";
 const UNHANDLED_ERROR: &str = "Unhandled error.";
 
 /// A struct to hold information for printing a useful error message to a user for their parser.
@@ -49,6 +50,7 @@ pub enum ExpectedConstruct {
     Number,
     ValidIdent,
     ExactText,
+    Escaped(char),
 }
 
 impl Display for ExpectedConstruct {
@@ -69,6 +71,7 @@ impl Display for ExpectedConstruct {
             ExpectedConstruct::Star => f.write_str("*"),
             ExpectedConstruct::NumberColonIdent => f.write_str("<number>:<Ident>"),
             ExpectedConstruct::Number => f.write_str("<number>"),
+            ExpectedConstruct::Escaped(c) => write!(f, "'\\{}'", c),
         }
     }
 }
@@ -127,6 +130,17 @@ impl<'a> YewRouterParseError<'a> {
         } else if dangling_query(substring) {
             offset = dangling_query_offset(input);
             (vec![Ec::Equals], DANGLING_QUERY.to_string())
+        } else if let Some(escape_offset) = invalid_escape_offset(input) {
+            offset = escape_offset;
+            (vec![], INVALID_ESCAPE.to_string())
+        } else if let Some(c) = unescaped_reserved_character(substring) {
+            (
+                vec![Ec::Escaped(c)],
+                format!(
+                    "The character '{}' is reserved. To match it literally, escape it with a backslash: '\\{}'.",
+                    c, c
+                ),
+            )
         } else if offset == 0 {
             (
                 vec![Ec::Slash, Ec::Question, Ec::Hash, Ec::OpenBrace],
@@ -283,6 +297,30 @@ fn dangling_query(substring: &str) -> bool {
     }
 }
 
+/// Finds the offset of the first `\` that isn't followed by an escapable character.
+fn invalid_escape_offset(input: &str) -> Option<usize> {
+    let mut chars = input.char_indices();
+    while let Some((index, c)) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some((_, escaped)) if ESCAPABLE_CHARACTERS.contains(escaped) => {}
+                _ => return Some(index),
+            }
+        }
+    }
+    None
+}
+
+/// Returns the character at the start of the substring if it is a reserved character that
+/// could be escaped in order to be matched literally.
+fn unescaped_reserved_character(substring: &str) -> Option<char> {
+    const RESERVED_CHARACTERS: &str = "()|}=& ";
+    substring
+        .chars()
+        .next()
+        .filter(|c| RESERVED_CHARACTERS.contains(*c))
+}
+
 fn dangling_query_offset(input: &str) -> usize {
     let (rest, _) = alt((
         terminated(skip_until(char('?')), valid_exact_match_characters),
@@ -381,6 +419,32 @@ mod test_conditions {
     fn dangling_query_avoids_false_positive_capture() {
         assert!(!dangling_query("?thing={}"))
     }
+
+    // ------
+    #[test]
+    fn invalid_escape_detected() {
+        assert_eq!(invalid_escape_offset(r"/lorem\ipsum"), Some(6))
+    }
+
+    #[test]
+    fn invalid_escape_detected_at_end() {
+        assert_eq!(invalid_escape_offset("/lorem\\"), Some(6))
+    }
+
+    #[test]
+    fn invalid_escape_avoids_false_positive() {
+        assert_eq!(invalid_escape_offset(r"/lorem\(\\\)"), None)
+    }
+
+    #[test]
+    fn unescaped_reserved_character_detected() {
+        assert_eq!(unescaped_reserved_character("(1).pdf"), Some('('))
+    }
+
+    #[test]
+    fn unescaped_reserved_character_avoids_false_positive() {
+        assert_eq!(unescaped_reserved_character("{capture}"), None)
+    }
 }
 
 #[cfg(test)]
@@ -572,6 +636,49 @@ Message:         'Double slashes ('//') are not allowed.'"##;
         assert_eq!(error, expected)
     }
 
+    // --------------
+
+    #[test]
+    fn unescaped_parenthesis() {
+        let input = "/files/report(1).pdf";
+        let error = parse(input).expect_err("should fail");
+
+        let expected = YewRouterParseError {
+            input,
+            offset: 13,
+            expected: vec![Ec::Escaped('(')],
+            reason: "The character '(' is reserved. To match it literally, escape it with a backslash: '\\('.".to_string(),
+        };
+        assert_eq!(error, expected)
+    }
+
+    #[test]
+    fn unescaped_equals_displays_correctly() {
+        let input = "/a=b";
+        let error = parse(input).expect_err("should fail");
+        let printed_error = format!("{}", error);
+        let expected = r##"
+/a=b
+--^
+Expected one of: '\='.
+Message:         'The character '=' is reserved. To match it literally, escape it with a backslash: '\='.'"##;
+        assert_eq!(printed_error, expected);
+    }
+
+    #[test]
+    fn invalid_escape() {
+        let input = r"/lorem\ipsum";
+        let error = parse(input).expect_err("should fail");
+
+        let expected = YewRouterParseError {
+            input,
+            offset: 6,
+            expected: vec![],
+            reason: INVALID_ESCAPE.to_string(),
+        };
+        assert_eq!(error, expected)
+    }
+
     #[test]
     fn dangling_query_before_valid_query() {
         let input = "?bad_query&query=thing";
@@ -4,6 +4,8 @@ use nom::branch::alt;
 use nom::combinator::{all_consuming, map_opt, opt};
 use nom::error::{context, VerboseError};
 use nom::sequence::tuple;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 mod core;
 mod error;
@@ -13,6 +15,7 @@ mod query;
 pub mod util;
 
 pub use error::YewRouterParseError;
+pub(crate) use self::core::{valid_ident_characters, ESCAPABLE_CHARACTERS};
 
 /// Alias for a tuple of optional vectors of tokens representing the path, query, and fragment in that order.
 type PathQueryFragmentTokens = (
@@ -51,6 +54,7 @@ pub enum RouteParserToken {
 /// Token representing various types of captures.
 ///
 /// It can capture and discard for unnamed variants, or capture and store in the `Matches` for the named variants.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
 pub enum CaptureVariant {
     /// {} - matches anything.
@@ -76,6 +80,7 @@ pub enum CaptureVariant {
 }
 
 /// A capture section with one of a  variety of capture types and a possible set of strings to restrict matching to.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
 pub struct Capture {
     /// The type of capture
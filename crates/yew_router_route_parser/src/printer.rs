@@ -0,0 +1,211 @@
+//! Printing of tokens back into a "matcher string".
+//!
+//! Printing tokens produced by `parse` and parsing the result again yields the same tokens.
+use crate::parser::{
+    Capture, CaptureOrExact, CaptureVariant, RouteParserToken, ESCAPABLE_CHARACTERS,
+};
+use crate::token_optimizer::MatcherToken;
+use std::fmt::{self, Display, Formatter, Write};
+
+/// Characters that are structural within the exact sections of `MatcherToken`s.
+///
+/// The optimizer merges separators and query delimiters into the surrounding exact text,
+/// so these are printed as-is instead of being escaped.
+const STRUCTURAL_CHARACTERS: &str = "?&#=";
+
+/// Writes the exact text, escaping reserved characters that aren't in `unescaped`.
+fn write_escaped(f: &mut Formatter, text: &str, unescaped: &str) -> fmt::Result {
+    for c in text.chars() {
+        if ESCAPABLE_CHARACTERS.contains(c) && !unescaped.contains(c) {
+            f.write_char('\\')?;
+        }
+        f.write_char(c)?;
+    }
+    Ok(())
+}
+
+impl Display for CaptureVariant {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            CaptureVariant::Unnamed => Ok(()),
+            CaptureVariant::ManyUnnamed => f.write_str("*"),
+            CaptureVariant::NumberedUnnamed { sections } => write!(f, "{}", sections),
+            CaptureVariant::Named(name) => f.write_str(name),
+            CaptureVariant::ManyNamed(name) => write!(f, "*:{}", name),
+            CaptureVariant::NumberedNamed { sections, name } => write!(f, "{}:{}", sections, name),
+        }
+    }
+}
+
+impl Display for Capture {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{{{}", self.capture_variant)?;
+        if let Some(allowed_captures) = &self.allowed_captures {
+            f.write_char('(')?;
+            for (index, allowed) in allowed_captures.iter().enumerate() {
+                if index != 0 {
+                    f.write_char('|')?;
+                }
+                write_escaped(f, allowed, "")?;
+            }
+            f.write_char(')')?;
+        }
+        f.write_char('}')
+    }
+}
+
+impl Display for CaptureOrExact {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            CaptureOrExact::Exact(text) => write_escaped(f, text, ""),
+            CaptureOrExact::Capture(capture) => capture.fmt(f),
+        }
+    }
+}
+
+impl Display for RouteParserToken {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            RouteParserToken::Separator => f.write_char('/'),
+            RouteParserToken::Exact(text) => write_escaped(f, text, ""),
+            RouteParserToken::Capture(capture) => capture.fmt(f),
+            RouteParserToken::QueryBegin => f.write_char('?'),
+            RouteParserToken::QuerySeparator => f.write_char('&'),
+            RouteParserToken::QueryCapture {
+                ident,
+                capture_or_match,
+            } => write!(f, "{}={}", ident, capture_or_match),
+            RouteParserToken::FragmentBegin => f.write_char('#'),
+            RouteParserToken::Optional(tokens) => {
+                write!(f, "[{}]", print_route_parser_tokens(tokens))
+            }
+        }
+    }
+}
+
+impl Display for MatcherToken {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            MatcherToken::Exact(text) => write_escaped(f, text, STRUCTURAL_CHARACTERS),
+            MatcherToken::Capture(capture) => capture.fmt(f),
+            MatcherToken::Optional(tokens) => write!(f, "[{}]", print_matcher_tokens(tokens)),
+        }
+    }
+}
+
+/// Prints `RouteParserToken`s as a "matcher string".
+pub fn print_route_parser_tokens(tokens: &[RouteParserToken]) -> String {
+    tokens.iter().map(ToString::to_string).collect()
+}
+
+/// Prints `MatcherToken`s as a "matcher string".
+///
+/// Because the optimizer doesn't keep track of which characters were escaped,
+/// an escaped `?`, `&`, `#`, or `=` will be printed without its escape.
+pub fn print_matcher_tokens(tokens: &[MatcherToken]) -> String {
+    tokens.iter().map(ToString::to_string).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::parse;
+    use crate::token_optimizer::parse_str_and_optimize_tokens;
+
+    fn route_parser_round_trip(i: &str) {
+        let tokens = parse(i).expect("should parse");
+        let printed = print_route_parser_tokens(&tokens);
+        assert_eq!(printed, i);
+        assert_eq!(parse(&printed).expect("printed should parse"), tokens);
+    }
+
+    fn matcher_round_trip(i: &str, append_optional_slash: bool) {
+        let tokens = parse_str_and_optimize_tokens(i, append_optional_slash).expect("should parse");
+        // Inserted optional slashes can't be parsed, so they have to be removed before printing.
+        let without_optional_slashes: Vec<MatcherToken> = tokens
+            .iter()
+            .filter(|token| {
+                **token != MatcherToken::Optional(vec![MatcherToken::Exact("/".to_string())])
+            })
+            .cloned()
+            .collect();
+        let printed = print_matcher_tokens(&without_optional_slashes);
+        let reparsed = parse_str_and_optimize_tokens(&printed, append_optional_slash)
+            .expect("printed should parse");
+        assert_eq!(reparsed, tokens);
+    }
+
+    const MATCHER_STRINGS: &[&str] = &[
+        "/",
+        "/lorem/ipsum",
+        "/lorem/",
+        "/lorem/{ipsum}",
+        "/lorem/{}dolor",
+        "/lorem/{*}",
+        "/lorem/{*:ipsum}",
+        "/lorem/{3}",
+        "/lorem/{3:ipsum}",
+        "/lorem/{ipsum(dolor|sit)}",
+        "/lorem/{(dolor|sit)}",
+        "/lorem[/{ipsum}]",
+        "/lorem/ipsum[/{dolor}]",
+        "/lorem?ipsum=dolor",
+        "/lorem?ipsum={dolor}&sit={amet}",
+        "?ipsum={dolor}[&sit={amet}]",
+        "/lorem#ipsum",
+        "#{ipsum}",
+        "/lorem?ipsum=dolor#{sit}",
+        r"/files/report\(1\).pdf",
+        r"/lorem\ ipsum/\[dolor\]",
+        r"/lorem/{ipsum(a\|b|c\)d)}",
+        r"/lorem\\ipsum",
+        "{ipsum}",
+        "{*:ipsum}",
+    ];
+
+    #[test]
+    fn route_parser_tokens_round_trip() {
+        MATCHER_STRINGS
+            .iter()
+            .for_each(|i| route_parser_round_trip(i))
+    }
+
+    #[test]
+    fn matcher_tokens_round_trip() {
+        MATCHER_STRINGS
+            .iter()
+            .for_each(|i| matcher_round_trip(i, false))
+    }
+
+    #[test]
+    fn matcher_tokens_with_optional_slash_round_trip() {
+        MATCHER_STRINGS
+            .iter()
+            .for_each(|i| matcher_round_trip(i, true))
+    }
+
+    #[test]
+    fn matcher_tokens_print_query_unescaped() {
+        let tokens =
+            parse_str_and_optimize_tokens("/lorem?ipsum={dolor}", false).expect("should parse");
+        assert_eq!(print_matcher_tokens(&tokens), "/lorem?ipsum={dolor}")
+    }
+
+    #[test]
+    fn matcher_tokens_print_inserted_optional_slash() {
+        let tokens = parse_str_and_optimize_tokens("/lorem", true).expect("should parse");
+        assert_eq!(print_matcher_tokens(&tokens), "/lorem[/]")
+    }
+
+    #[test]
+    fn capture_display() {
+        let capture = Capture {
+            capture_variant: CaptureVariant::NumberedNamed {
+                sections: 2,
+                name: "lorem".to_string(),
+            },
+            allowed_captures: Some(vec!["ipsum".to_string(), "dolor sit".to_string()]),
+        };
+        assert_eq!(capture.to_string(), r"{2:lorem(ipsum|dolor\ sit)}")
+    }
+}
@@ -0,0 +1,138 @@
+//! Ranking of matchers by how specific they are.
+use crate::parser::CaptureVariant;
+use crate::token_optimizer::MatcherToken;
+use std::cmp::Ordering;
+
+/// How specific a matcher is, used to choose between matchers that match the same route.
+///
+/// Specificities are ordered so that the more specific matcher is the greater one:
+/// * Matchers that match more bytes exactly are more specific,
+///   so `/users/new` is more specific than `/users/{id}`.
+/// * Then, matchers with fewer `{*}` captures are more specific,
+///   followed by matchers with fewer `{4}` captures, and then fewer `{}` captures.
+/// * Then, matchers with less deeply nested optional sections are more specific.
+///
+/// It is calculated from the tokens of a matcher by `Specificity::of`,
+/// so the order of matchers can be known before they are used, like when deriving `Switch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Specificity {
+    /// The number of bytes matched exactly, outside of optional sections.
+    pub exact_bytes: usize,
+    /// The number of captures that match a single section, like `{}` or `{name}`.
+    pub section_captures: usize,
+    /// The number of captures that match a number of sections, like `{4}` or `{4:name}`.
+    pub numbered_captures: usize,
+    /// The number of captures that match any number of sections, like `{*}` or `{*:name}`.
+    pub many_captures: usize,
+    /// How deeply optional sections are nested.
+    pub optional_depth: usize,
+}
+
+impl Specificity {
+    /// Calculates the specificity of the tokens.
+    pub fn of(tokens: &[MatcherToken]) -> Self {
+        let mut specificity = Specificity::default();
+        specificity.add(tokens, 0);
+        specificity
+    }
+
+    fn add(&mut self, tokens: &[MatcherToken], depth: usize) {
+        self.optional_depth = self.optional_depth.max(depth);
+        for token in tokens {
+            match token {
+                MatcherToken::Exact(literal) => {
+                    if depth == 0 {
+                        self.exact_bytes += literal.len();
+                    }
+                }
+                MatcherToken::Capture(capture) => match capture.capture_variant {
+                    CaptureVariant::Unnamed | CaptureVariant::Named(_) => {
+                        self.section_captures += 1
+                    }
+                    CaptureVariant::NumberedUnnamed { .. }
+                    | CaptureVariant::NumberedNamed { .. } => self.numbered_captures += 1,
+                    CaptureVariant::ManyUnnamed | CaptureVariant::ManyNamed(_) => {
+                        self.many_captures += 1
+                    }
+                },
+                MatcherToken::Optional(inner) => self.add(inner, depth + 1),
+            }
+        }
+    }
+}
+
+impl Ord for Specificity {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Fewer captures and shallower optional sections are more specific,
+        // so those comparisons are reversed.
+        self.exact_bytes
+            .cmp(&other.exact_bytes)
+            .then_with(|| other.many_captures.cmp(&self.many_captures))
+            .then_with(|| other.numbered_captures.cmp(&self.numbered_captures))
+            .then_with(|| other.section_captures.cmp(&self.section_captures))
+            .then_with(|| other.optional_depth.cmp(&self.optional_depth))
+    }
+}
+
+impl PartialOrd for Specificity {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::token_optimizer::parse_str_and_optimize_tokens;
+
+    fn specificity(i: &str) -> Specificity {
+        Specificity::of(&parse_str_and_optimize_tokens(i, false).expect("should parse"))
+    }
+
+    fn assert_more_specific(more: &str, less: &str) {
+        assert!(
+            specificity(more) > specificity(less),
+            "'{}' should be more specific than '{}'",
+            more,
+            less
+        );
+    }
+
+    #[test]
+    fn exact_bytes_are_more_specific_than_captures() {
+        assert_more_specific("/users/new", "/users/{id}");
+        assert_more_specific("/users/{id}/edit", "/users/{id}");
+        assert_more_specific("/users/{id}", "/users[/{id}]");
+    }
+
+    #[test]
+    fn capture_kinds() {
+        assert_more_specific("/users/{id}", "/users/{2:id}");
+        assert_more_specific("/users/{2:id}", "/users/{*:id}");
+        assert_more_specific("/users/{id}", "/users/{*:id}");
+    }
+
+    #[test]
+    fn optional_depth() {
+        let shallow = Specificity {
+            exact_bytes: 6,
+            optional_depth: 1,
+            ..Default::default()
+        };
+        let deep = Specificity {
+            optional_depth: 2,
+            ..shallow
+        };
+        assert!(shallow > deep);
+    }
+
+    #[test]
+    fn counts() {
+        let specificity = specificity("/users/{id}/{*:rest}[/{2:sections}]");
+        assert_eq!(specificity.exact_bytes, 8);
+        assert_eq!(specificity.section_captures, 1);
+        assert_eq!(specificity.many_captures, 1);
+        assert_eq!(specificity.numbered_captures, 1);
+        assert_eq!(specificity.optional_depth, 1);
+    }
+}
@@ -9,8 +9,11 @@ use crate::parser::util::alternative;
 use crate::parser::YewRouterParseError;
 use nom::branch::alt;
 use nom::combinator::{cond, map_opt, rest};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Tokens used to determine how to match and capture sections from a URL.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, PartialEq, Clone)]
 pub enum MatcherToken {
     /// Section-related tokens can be condensed into a match.
@@ -264,6 +267,25 @@ mod test {
         )
     }
 
+    #[test]
+    fn escaped_characters_are_unescaped_in_exact_token() {
+        let optimized = parse_str_and_optimize_tokens(r"/files/report\(1\).pdf", false)
+            .expect("should parse");
+        let expected = vec![MatcherToken::Exact("/files/report(1).pdf".to_string())];
+        assert_eq!(expected, optimized);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn matcher_tokens_serde_round_trip() {
+        let tokens = parse_str_and_optimize_tokens("/lorem/{ipsum(a|b)}[/{*:dolor}]", true)
+            .expect("should parse");
+        let serialized = serde_json::to_string(&tokens).expect("should serialize");
+        let deserialized: Vec<MatcherToken> =
+            serde_json::from_str(&serialized).expect("should deserialize");
+        assert_eq!(deserialized, tokens);
+    }
+
     #[test]
     fn optimization_inserts_optional_slash_at_end() {
         let tokens = vec![
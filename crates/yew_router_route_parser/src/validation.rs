@@ -0,0 +1,203 @@
+//! Validation of `MatcherToken`s that weren't produced by the parser.
+use crate::parser::{valid_ident_characters, Capture, CaptureVariant};
+use crate::token_optimizer::MatcherToken;
+use nom::combinator::all_consuming;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// Reasons why a sequence of `MatcherToken`s could not have been produced by the parser.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidMatcherTokens {
+    /// An exact section doesn't contain any text.
+    EmptyExact,
+    /// An optional section doesn't contain any tokens.
+    EmptyOptional,
+    /// Two captures may be next to each other, possibly separated by optional sections.
+    AdjacentCaptures,
+    /// A capture's name is not a valid identifier.
+    InvalidCaptureName(String),
+}
+
+impl Display for InvalidMatcherTokens {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            InvalidMatcherTokens::EmptyExact => f.write_str("Exact sections can't be empty."),
+            InvalidMatcherTokens::EmptyOptional => f.write_str("Optional sections can't be empty."),
+            InvalidMatcherTokens::AdjacentCaptures => f.write_str(
+                "Captures can't be next to each other. Optional sections do not separate captures, because they may not match at all.",
+            ),
+            InvalidMatcherTokens::InvalidCaptureName(name) => {
+                write!(f, "'{}' is not a valid capture name.", name)
+            }
+        }
+    }
+}
+
+impl Error for InvalidMatcherTokens {}
+
+/// Checks that the tokens uphold the invariants that the parser enforces.
+///
+/// This is useful for tokens that come from somewhere other than the parser,
+/// like a deserialized config file.
+pub fn validate_matcher_tokens(tokens: &[MatcherToken]) -> Result<(), InvalidMatcherTokens> {
+    validate_impl(tokens, false).map(|_| ())
+}
+
+/// Returns whether the sequence of tokens may end with a capture.
+fn validate_impl(
+    tokens: &[MatcherToken],
+    mut follows_capture: bool,
+) -> Result<bool, InvalidMatcherTokens> {
+    for token in tokens {
+        match token {
+            MatcherToken::Exact(text) => {
+                if text.is_empty() {
+                    return Err(InvalidMatcherTokens::EmptyExact);
+                }
+                follows_capture = false;
+            }
+            MatcherToken::Capture(capture) => {
+                if follows_capture {
+                    return Err(InvalidMatcherTokens::AdjacentCaptures);
+                }
+                validate_capture(capture)?;
+                follows_capture = true;
+            }
+            MatcherToken::Optional(inner) => {
+                if inner.is_empty() {
+                    return Err(InvalidMatcherTokens::EmptyOptional);
+                }
+                // The optional section may be skipped, so a preceding capture still counts.
+                follows_capture |= validate_impl(inner, follows_capture)?;
+            }
+        }
+    }
+    Ok(follows_capture)
+}
+
+fn validate_capture(capture: &Capture) -> Result<(), InvalidMatcherTokens> {
+    match &capture.capture_variant {
+        CaptureVariant::Named(name)
+        | CaptureVariant::ManyNamed(name)
+        | CaptureVariant::NumberedNamed { name, .. } => {
+            if all_consuming(valid_ident_characters)(name).is_err() {
+                return Err(InvalidMatcherTokens::InvalidCaptureName(name.clone()));
+            }
+        }
+        CaptureVariant::Unnamed
+        | CaptureVariant::ManyUnnamed
+        | CaptureVariant::NumberedUnnamed { .. } => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::token_optimizer::parse_str_and_optimize_tokens;
+
+    fn named(name: &str) -> MatcherToken {
+        MatcherToken::Capture(Capture::from(CaptureVariant::Named(name.to_string())))
+    }
+
+    fn exact(text: &str) -> MatcherToken {
+        MatcherToken::Exact(text.to_string())
+    }
+
+    #[test]
+    fn parsed_tokens_are_valid() {
+        for i in &[
+            "/lorem/{ipsum}",
+            "/lorem[/{ipsum}]",
+            "/{lorem}/{*:ipsum}?dolor={sit}#{amet}",
+            "{*:lorem}",
+        ] {
+            let tokens = parse_str_and_optimize_tokens(i, true).expect("should parse");
+            assert_eq!(validate_matcher_tokens(&tokens), Ok(()));
+        }
+    }
+
+    #[test]
+    fn adjacent_captures_are_invalid() {
+        let tokens = vec![exact("/"), named("lorem"), named("ipsum")];
+        assert_eq!(
+            validate_matcher_tokens(&tokens),
+            Err(InvalidMatcherTokens::AdjacentCaptures)
+        );
+    }
+
+    #[test]
+    fn captures_separated_by_optional_are_invalid() {
+        let tokens = vec![
+            named("lorem"),
+            MatcherToken::Optional(vec![exact("-")]),
+            named("ipsum"),
+        ];
+        assert_eq!(
+            validate_matcher_tokens(&tokens),
+            Err(InvalidMatcherTokens::AdjacentCaptures)
+        );
+    }
+
+    #[test]
+    fn capture_at_start_of_optional_after_capture_is_invalid() {
+        let tokens = vec![
+            named("lorem"),
+            MatcherToken::Optional(vec![named("ipsum"), exact("/")]),
+        ];
+        assert_eq!(
+            validate_matcher_tokens(&tokens),
+            Err(InvalidMatcherTokens::AdjacentCaptures)
+        );
+    }
+
+    #[test]
+    fn capture_after_optional_ending_with_capture_is_invalid() {
+        let tokens = vec![
+            exact("/"),
+            MatcherToken::Optional(vec![exact("lorem/"), named("ipsum")]),
+            named("dolor"),
+        ];
+        assert_eq!(
+            validate_matcher_tokens(&tokens),
+            Err(InvalidMatcherTokens::AdjacentCaptures)
+        );
+    }
+
+    #[test]
+    fn captures_separated_by_exact_are_valid() {
+        let tokens = vec![
+            named("lorem"),
+            MatcherToken::Optional(vec![exact("-")]),
+            exact("/"),
+            named("ipsum"),
+        ];
+        assert_eq!(validate_matcher_tokens(&tokens), Ok(()));
+    }
+
+    #[test]
+    fn empty_sections_are_invalid() {
+        assert_eq!(
+            validate_matcher_tokens(&[exact("")]),
+            Err(InvalidMatcherTokens::EmptyExact)
+        );
+        assert_eq!(
+            validate_matcher_tokens(&[exact("/"), MatcherToken::Optional(vec![])]),
+            Err(InvalidMatcherTokens::EmptyOptional)
+        );
+    }
+
+    #[test]
+    fn invalid_capture_name() {
+        assert_eq!(
+            validate_matcher_tokens(&[exact("/"), named("lorem ipsum")]),
+            Err(InvalidMatcherTokens::InvalidCaptureName(
+                "lorem ipsum".to_string()
+            ))
+        );
+        assert_eq!(
+            validate_matcher_tokens(&[exact("/"), named("")]),
+            Err(InvalidMatcherTokens::InvalidCaptureName("".to_string()))
+        );
+    }
+}
@@ -27,7 +27,7 @@ impl Component for Guide {
     type Properties = GuideProps;
 
     fn create(props: Self::Properties, mut link: ComponentLink<Self>) -> Self {
-        let callback = link.send_back(Msg::UpdateRoute);
+        let callback = link.send_back(|change: RouteChange| Msg::UpdateRoute(change.current));
         let router_agent = RouteAgent::bridge(callback);
         Guide {
             router_agent,
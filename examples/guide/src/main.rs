@@ -5,6 +5,8 @@ use guide::{Guide, Page};
 fn main() {
     yew::initialize();
     web_logger::init();
+    // Same-origin links in the rendered markdown change the route, rather than reloading the page.
+    yew_router::RouterConfig::new().link_interception(true).install();
     App::<Model>::new().mount_to_body();
     yew::run_loop();
 }
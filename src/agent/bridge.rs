@@ -1,6 +1,7 @@
 //! Bridge to RouteAgent.
-use crate::agent::{AgentState, RouteAgent};
+use crate::agent::{AgentState, RouteAgent, RouteChange, RouteFilter, RouteRequest};
 use crate::route::Route;
+use crate::Switch;
 use std::fmt::{Debug, Error as FmtError, Formatter};
 use std::ops::{Deref, DerefMut};
 use yew::agent::Bridged;
@@ -8,6 +9,9 @@ use yew::agent::Context;
 use yew::{Bridge, Callback};
 
 /// A simplified interface to the router agent.
+///
+/// Dropping the bridge disconnects it from the agent, which removes it from the agent's
+/// subscribers, so its callback isn't called anymore.
 pub struct RouteAgentBridge<T>(Box<dyn Bridge<RouteAgent<T>>>)
 where
     for<'de> T: AgentState<'de>;
@@ -17,16 +21,45 @@ where
     for<'de> T: AgentState<'de>,
 {
     /// Creates a new bridge.
+    ///
+    /// The callback is called with the current route whenever it changes.
     pub fn new(callback: Callback<Route<T>>) -> Self {
+        Self::with_changes(callback.reform(|change: RouteChange<T>| change.current))
+    }
+
+    /// Creates a new bridge.
+    ///
+    /// The callback is called with the previous and current routes whenever the route changes.
+    pub fn with_changes(callback: Callback<RouteChange<T>>) -> Self {
         let router_agent = RouteAgent::bridge(callback);
         RouteAgentBridge(router_agent)
     }
 
+    /// Creates a new bridge that is only called when the route enters, leaves or changes within
+    /// the routes matched by the filter.
+    pub fn with_filter(filter: RouteFilter, callback: Callback<RouteChange<T>>) -> Self {
+        let mut bridge = Self::with_changes(callback);
+        bridge.send(RouteRequest::SetFilter(Some(filter)));
+        bridge
+    }
+
+    /// Creates a new bridge that is only called when the route enters, leaves or changes within
+    /// the routes that the `Switch` can be produced from.
+    ///
+    /// The callback is called with the `Switch` produced from the current route,
+    /// which is `None` once the route has left them.
+    pub fn with_switch<SW: Switch + 'static>(callback: Callback<Option<SW>>) -> Self {
+        let mut bridge = Self::new(callback.reform(|route: Route<T>| SW::switch(route)));
+        bridge.send(RouteRequest::SetFilter(Some(RouteFilter::of::<SW>())));
+        bridge
+    }
+
     /// Experimental, may be removed
     ///
     /// Directly spawn a new Router
     pub fn spawn(callback: Callback<Route<T>>) -> Self {
         use yew::agent::Discoverer;
+        let callback = callback.reform(|change: RouteChange<T>| change.current);
         let router_agent = Context::spawn_or_join(Some(callback));
         RouteAgentBridge(router_agent)
     }
@@ -0,0 +1,111 @@
+//! Filters that limit the route changes sent to a subscriber of the RouteAgent.
+use crate::agent::RouteChange;
+use crate::matcher::RouteMatcher;
+use crate::Switch;
+use serde::{Deserialize, Serialize};
+
+/// Limits the route changes that the `RouteAgent` sends to a subscriber
+/// to those where the route enters, leaves or changes within the routes matched by its matchers.
+///
+/// It is set by sending a `RouteRequest::SetFilter` to the agent.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RouteFilter {
+    matchers: Vec<RouteMatcher>,
+}
+
+impl RouteFilter {
+    /// Creates a filter that matches the routes that any of the matchers match.
+    pub fn new(matchers: Vec<RouteMatcher>) -> Self {
+        RouteFilter { matchers }
+    }
+
+    /// Creates a filter that matches the routes that the matchers of a `Switch` match.
+    ///
+    /// The matchers are acquired from `Switch::describe`,
+    /// so if the `Switch` isn't derived, the filter won't match any route.
+    pub fn of<SW: Switch>() -> Self {
+        let matchers = SW::describe()
+            .into_iter()
+            .map(|description| description.matcher)
+            .collect();
+        RouteFilter { matchers }
+    }
+
+    /// Gets the matchers.
+    pub fn matchers(&self) -> &[RouteMatcher] {
+        &self.matchers
+    }
+
+    /// Determines if any of the matchers match the route.
+    pub fn matches(&self, route: &str) -> bool {
+        self.matchers
+            .iter()
+            .any(|matcher| matcher.capture_route_into_map(route).is_ok())
+    }
+
+    /// Determines if the change should be sent to the subscriber.
+    ///
+    /// This is the case if the route entered or left the matched routes,
+    /// or if it changed to another one of them.
+    pub fn is_relevant<T>(&self, change: &RouteChange<T>) -> bool {
+        match &change.previous {
+            Some(previous) => {
+                previous.route != change.current.route
+                    && (self.matches(&previous.route) || self.matches(&change.current.route))
+            }
+            None => self.matches(&change.current.route),
+        }
+    }
+}
+
+impl From<RouteMatcher> for RouteFilter {
+    fn from(matcher: RouteMatcher) -> Self {
+        RouteFilter::new(vec![matcher])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::agent::RouteChangeCause;
+    use crate::route::Route;
+
+    fn change(previous: &str, current: &str) -> RouteChange<()> {
+        RouteChange {
+            previous: Some(Route::from(previous)),
+            current: Route::from(current),
+            cause: RouteChangeCause::Push,
+        }
+    }
+
+    fn filter() -> RouteFilter {
+        RouteFilter::from(RouteMatcher::try_from("/users/{id}").expect("should parse"))
+    }
+
+    #[test]
+    fn entering_and_leaving_are_relevant() {
+        assert!(filter().is_relevant(&change("/about", "/users/12")));
+        assert!(filter().is_relevant(&change("/users/12", "/about")));
+    }
+
+    #[test]
+    fn changing_within_is_relevant() {
+        assert!(filter().is_relevant(&change("/users/12", "/users/13")));
+        assert!(!filter().is_relevant(&change("/users/12", "/users/12")));
+    }
+
+    #[test]
+    fn changing_outside_is_irrelevant() {
+        assert!(!filter().is_relevant(&change("/about", "/posts")));
+    }
+
+    #[test]
+    fn initial_route() {
+        let mut initial = change("", "/users/12");
+        initial.previous = None;
+        initial.cause = RouteChangeCause::Initial;
+        assert!(filter().is_relevant(&initial));
+        initial.current = Route::from("/about");
+        assert!(!filter().is_relevant(&initial));
+    }
+}
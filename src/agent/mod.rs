@@ -2,11 +2,15 @@
 //!
 //! It wraps a route service and allows calls to be sent to it to update every subscriber,
 //! or just the element that made the request.
+use crate::config::RouterConfig;
 use crate::service::RouteService;
 
 use yew::prelude::worker::*;
+use yew::Callback;
 
-use std::collections::HashSet;
+use std::any::TypeId;
+use std::cell::Cell;
+use std::mem;
 
 use serde::Deserialize;
 use serde::Serialize;
@@ -14,7 +18,8 @@ use std::fmt::{Debug, Error as FmtError, Formatter};
 
 use crate::route::Route;
 use crate::route::RouteState;
-use log::trace;
+use crate::route::StateError;
+use log::{error, trace};
 
 mod bridge;
 pub use bridge::RouteAgentBridge;
@@ -22,6 +27,15 @@ pub use bridge::RouteAgentBridge;
 mod dispatcher;
 pub use dispatcher::RouteAgentDispatcher;
 
+mod filter;
+pub use filter::RouteFilter;
+
+mod subscribers;
+use subscribers::Subscribers;
+
+pub use crate::config::{ScrollBehavior, StateErrorFn, TitleFn};
+pub use crate::route::{RouteChange, RouteChangeCause};
+
 /// Any state that can be used in the router agent must meet the criteria of this trait.
 pub trait AgentState<'de>:
     RouteState + Serialize + Deserialize<'de> + Debug
@@ -32,6 +46,29 @@ impl<'de, T> AgentState<'de> for T where
 {
 }
 
+thread_local! {
+    /// The state type of the first `RouteAgent` that was created.
+    static AGENT_STATE_TYPE: Cell<Option<(TypeId, &'static str)>> = Cell::new(None);
+}
+
+/// Records the state type of an agent that is being created.
+///
+/// Returns the name of the state type that was recorded first, if it is a different one.
+fn record_state_type(
+    recorded: &Cell<Option<(TypeId, &'static str)>>,
+    id: TypeId,
+    name: &'static str,
+) -> Option<&'static str> {
+    match recorded.get() {
+        Some((recorded_id, recorded_name)) if recorded_id != id => Some(recorded_name),
+        Some(_) => None,
+        None => {
+            recorded.set(Some((id, name)));
+            None
+        }
+    }
+}
+
 /// Non-instantiable type.
 #[derive(Serialize, Deserialize, Clone, Copy, Debug)]
 pub enum Void {}
@@ -41,6 +78,8 @@ pub enum Void {}
 pub enum Msg<T> {
     /// Message for when the route is changed.
     BrowserNavigationRouteChanged((String, T)),
+    /// Message for when a link to the route was clicked.
+    LinkClicked(String),
 }
 
 /// Input message type for interacting with the `RouteAgent'.
@@ -56,20 +95,34 @@ pub enum RouteRequest<T> {
     ChangeRouteNoBroadcast(Route<T>),
     /// Gets the current route.
     GetCurrentRoute,
-    /// Removes the entity from the Router Agent
-    // TODO this is a temporary message because yew currently doesn't call the destructor, so it must be manually engaged
-    Disconnect,
+    /// Sets the filter that limits the route changes that the entity is sent.
+    /// `None` removes the filter, so the entity is sent every route change.
+    ///
+    /// The current route is always sent in response to `GetCurrentRoute`.
+    SetFilter(Option<RouteFilter>),
 }
 
 /// The RouteAgent holds on to the RouteService singleton and mediates access to it.
 ///
 /// It serves as a means to propagate messages to components interested in the state of the current route.
+/// Every subscriber is sent a `RouteChange` when the route changes,
+/// which holds the previous route and the cause of the change alongside the current one.
+///
+/// If the application is served under a base path, set by the `RouterConfig` or by a
+/// `<base href>` element, the base path is stripped from the routes that it sends to
+/// subscribers, and prepended to the routes that it is asked to change to.
+///
+/// The `RouterConfig` also sets whether it saves and restores the scroll position,
+/// scrolls to fragments, sets the document's title, versions and persists route states,
+/// and turns clicks on ordinary links into route changes.
 ///
 /// # Warning
 /// All routing-related components should use the same type parameter across your application.
 ///
 /// If you don't, then multiple RouteAgents will be spawned, and will not communicate messages to
 /// routing components of different types.
+/// An error is logged when a RouteAgent is created with a different type parameter
+/// than the first one.
 ///
 pub struct RouteAgent<T>
 where
@@ -78,10 +131,15 @@ where
     // In order to have the AgentLink<Self> below, apparently T must be constrained like this. Unfortunately, this means that everything related to an agent requires this constraint.
     link: AgentLink<RouteAgent<T>>,
     route_service: RouteService<T>,
-    /// A list of all entities connected to the router.
+    /// A list of all entities connected to the router, along with their filters.
     /// When a route changes, either initiated by the browser or by the app,
-    /// the route change will be broadcast to all listening entities.
-    subscribers: HashSet<HandlerId>,
+    /// the route change will be broadcast to all listening entities whose filters it passes.
+    subscribers: Subscribers<HandlerId>,
+    /// The most recent route, which becomes the previous route when the route changes.
+    route: Route<T>,
+    scroll_behavior: ScrollBehavior,
+    title_fn: Option<TitleFn>,
+    state_persistence: bool,
 }
 
 impl<T: for<'de> AgentState<'de>> Debug for RouteAgent<T> {
@@ -90,6 +148,10 @@ impl<T: for<'de> AgentState<'de>> Debug for RouteAgent<T> {
             .field("link", &"-")
             .field("route_service", &self.route_service)
             .field("subscribers", &self.subscribers.len())
+            .field("route", &self.route)
+            .field("scroll_behavior", &self.scroll_behavior)
+            .field("title_fn", &self.title_fn)
+            .field("state_persistence", &self.state_persistence)
             .finish()
     }
 }
@@ -101,84 +163,243 @@ where
     type Reach = Context;
     type Message = Msg<T>;
     type Input = RouteRequest<T>;
-    type Output = Route<T>;
+    type Output = RouteChange<T>;
 
     fn create(link: AgentLink<RouteAgent<T>>) -> Self {
-        let callback = link.send_back(Msg::BrowserNavigationRouteChanged);
-        let mut route_service = RouteService::new();
-        route_service.register_callback(callback);
-
-        RouteAgent {
-            link,
-            route_service,
-            subscribers: HashSet::new(),
+        let state_type = std::any::type_name::<T>();
+        let first_state_type = AGENT_STATE_TYPE
+            .with(|recorded| record_state_type(recorded, TypeId::of::<T>(), state_type));
+        if let Some(first_state_type) = first_state_type {
+            error!(
+                "A RouteAgent<{}> was created, but a RouteAgent<{}> already exists. \
+                 They don't send route changes to each other's subscribers, \
+                 so every routing component should use the same state type.",
+                state_type, first_state_type
+            );
         }
+        RouterConfig::with_installed(|config| {
+            let callback = link.send_back(Msg::BrowserNavigationRouteChanged);
+            let mut route_service = RouteService::new();
+            if let Some(configure) = config.configure_versioning::<T>() {
+                configure(&mut route_service);
+            }
+            if let Some(state_error_fn) = config.state_error_fn {
+                let callback = Callback::from(move |error: StateError| state_error_fn(&error));
+                route_service.set_state_error_callback(callback);
+            }
+            route_service.register_callback(callback);
+            if config.link_interception {
+                route_service.intercept_links(link.send_back(Msg::LinkClicked));
+            }
+            if config.scroll_behavior.restore_position {
+                route_service.set_manual_scroll_restoration();
+            }
+
+            let mut agent = RouteAgent {
+                link,
+                route_service,
+                subscribers: Subscribers::new(),
+                route: Route::default(),
+                scroll_behavior: config.scroll_behavior,
+                title_fn: config.title_fn,
+                state_persistence: config.state_persistence,
+            };
+            agent.route = agent.current_route();
+            agent
+        })
     }
 
     fn update(&mut self, msg: Self::Message) {
         match msg {
             Msg::BrowserNavigationRouteChanged((_route_string, state)) => {
                 trace!("Browser navigated");
-                let mut route = Route::current_route(&self.route_service);
-                route.state = Some(state);
-                for sub in &self.subscribers {
-                    self.link.response(*sub, route.clone());
+                let mut route = self.current_route();
+                if route.state.is_none() {
+                    route.state = Some(state);
+                }
+                self.route_service.set_title(&self.title(&route.route));
+                self.broadcast(route, RouteChangeCause::Pop);
+                if self.scroll_behavior.restore_position {
+                    if let Some((x, y)) = self.route_service.scroll_position() {
+                        self.route_service.scroll_to(x, y);
+                    }
                 }
             }
+            Msg::LinkClicked(route) => {
+                trace!("Link clicked");
+                self.change_route(Route::from(route));
+            }
         }
     }
 
     fn connected(&mut self, id: HandlerId) {
-        self.subscribers.insert(id);
+        self.subscribers.subscribe(id);
     }
 
     fn handle(&mut self, msg: Self::Input, who: HandlerId) {
         match msg {
             RouteRequest::ReplaceRoute(route) => {
                 let route_string: String = route.to_string();
-                self.route_service.replace_route(
+                let title = self.title(&route_string);
+                // Replacing the entry changes its id, so the state persisted for it is removed.
+                self.persist_state(None);
+                self.route_service.replace_route_with_title(
                     &route_string,
-                    route.state.unwrap_or_default(),
+                    route.state.clone().unwrap_or_default(),
+                    &title,
                 );
-                let route = Route::current_route(&self.route_service);
-                for sub in &self.subscribers {
-                    self.link.response(*sub, route.clone());
-                }
+                self.persist_state(route.state.as_ref());
+                let route = self.current_route();
+                self.broadcast(route, RouteChangeCause::Replace);
             }
             RouteRequest::ReplaceRouteNoBroadcast(route) => {
                 let route_string: String = route.to_string();
-                self.route_service.replace_route(
+                let title = self.title(&route_string);
+                // Replacing the entry changes its id, so the state persisted for it is removed.
+                self.persist_state(None);
+                self.route_service.replace_route_with_title(
                     &route_string,
-                    route.state.unwrap_or_default(),
+                    route.state.clone().unwrap_or_default(),
+                    &title,
                 );
+                self.persist_state(route.state.as_ref());
+                self.route = self.current_route();
             }
             RouteRequest::ChangeRoute(route) => {
-                let route_string: String = route.to_string();
-                // set the route
-                self.route_service
-                    .set_route(&route_string, route.state.unwrap_or_default());
-                // get the new route. This will contain a default state object
-                let route = Route::current_route(&self.route_service);
-                // broadcast it to all listening components
-                for sub in &self.subscribers {
-                    self.link.response(*sub, route.clone());
-                }
+                self.change_route(route);
             }
             RouteRequest::ChangeRouteNoBroadcast(route) => {
                 let route_string: String = route.to_string();
-                self.route_service
-                    .set_route(&route_string, route.state.unwrap_or_default());
+                self.save_scroll_position();
+                let title = self.title(&route_string);
+                self.route_service.set_route_with_title(
+                    &route_string,
+                    route.state.clone().unwrap_or_default(),
+                    &title,
+                );
+                self.persist_state(route.state.as_ref());
+                self.route = self.current_route();
             }
             RouteRequest::GetCurrentRoute => {
-                let route = Route::current_route(&self.route_service);
-                self.link.response(who, route.clone());
+                let change = RouteChange {
+                    previous: None,
+                    current: self.current_route(),
+                    cause: RouteChangeCause::Initial,
+                };
+                self.respond(&[who], &change);
             }
-            RouteRequest::Disconnect => {
-                self.disconnected(who);
+            RouteRequest::SetFilter(filter) => {
+                self.subscribers.set_filter(who, filter);
             }
         }
     }
     fn disconnected(&mut self, id: HandlerId) {
-        self.subscribers.remove(&id);
+        // Bridges are disconnected when they are dropped, so their ids are never used again.
+        self.subscribers.unsubscribe(id);
+    }
+}
+
+impl<T> RouteAgent<T>
+where
+    for<'de> T: AgentState<'de>,
+{
+    /// Changes the route, and broadcasts the change.
+    fn change_route(&mut self, route: Route<T>) {
+        let route_string: String = route.to_string();
+        self.save_scroll_position();
+        let title = self.title(&route_string);
+        // set the route
+        self.route_service.set_route_with_title(
+            &route_string,
+            route.state.clone().unwrap_or_default(),
+            &title,
+        );
+        self.persist_state(route.state.as_ref());
+        // get the new route. This will contain a default state object,
+        // unless the state is persisted
+        let route = self.current_route();
+        // broadcast it to all listening components
+        self.broadcast(route, RouteChangeCause::Push);
+        self.scroll_to_new_route();
+    }
+
+    /// Sends the change from the most recent route to the new one to every subscriber
+    /// whose filter it passes.
+    fn broadcast(&mut self, route: Route<T>, cause: RouteChangeCause) {
+        let previous = mem::replace(&mut self.route, route.clone());
+        let change = RouteChange {
+            previous: Some(previous),
+            current: route,
+            cause,
+        };
+        let subscribers = self.subscribers.recipients(&change);
+        self.respond(&subscribers, &change);
+    }
+
+    /// Sends the change to the subscribers.
+    fn respond(&self, subscribers: &[HandlerId], change: &RouteChange<T>) {
+        for sub in subscribers {
+            self.link.response(*sub, change.clone());
+        }
+    }
+
+    /// Gets the current route, with its persisted state if state persistence is enabled.
+    fn current_route(&mut self) -> Route<T> {
+        let mut route = Route::current_route(&self.route_service);
+        if self.state_persistence {
+            route.state = self
+                .route_service
+                .persisted_state()
+                .and_then(|serialized| serde_json::from_str(&serialized).ok());
+        }
+        route
+    }
+
+    /// Persists the state of the current history entry, if state persistence is enabled.
+    fn persist_state(&mut self, state: Option<&T>) {
+        if self.state_persistence {
+            let serialized = state.and_then(|state| serde_json::to_string(state).ok());
+            self.route_service
+                .persist_state(serialized.as_ref().map(String::as_str));
+        }
+    }
+
+    /// Gets the title of the route, or an empty title if it doesn't have one.
+    fn title(&self, route: &str) -> String {
+        self.title_fn
+            .and_then(|title_fn| title_fn(route))
+            .unwrap_or_default()
+    }
+
+    /// Saves the scroll position of the route that is being navigated away from.
+    fn save_scroll_position(&mut self) {
+        if self.scroll_behavior.restore_position {
+            self.route_service.save_scroll_position();
+        }
+    }
+
+    /// Scrolls to the fragment of the route that was changed to, or else to the top of the page.
+    fn scroll_to_new_route(&self) {
+        let anchored =
+            self.scroll_behavior.anchor_fragment && self.route_service.scroll_to_fragment();
+        if !anchored && self.scroll_behavior.restore_position {
+            self.route_service.scroll_to(0.0, 0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn different_state_types_are_detected() {
+        let recorded = Cell::new(None);
+        assert_eq!(record_state_type(&recorded, TypeId::of::<()>(), "()"), None);
+        assert_eq!(record_state_type(&recorded, TypeId::of::<()>(), "()"), None);
+        assert_eq!(
+            record_state_type(&recorded, TypeId::of::<String>(), "String"),
+            Some("()")
+        );
     }
 }
@@ -0,0 +1,111 @@
+//! The entities that the RouteAgent sends route changes to.
+use crate::agent::{RouteChange, RouteFilter};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The subscribers of the `RouteAgent`, along with their filters.
+///
+/// Subscribers are removed when their bridges are dropped,
+/// and a removed subscriber isn't added again by requests that were sent before it was removed,
+/// so it isn't sent any more route changes.
+#[derive(Debug)]
+pub(crate) struct Subscribers<K> {
+    filters: HashMap<K, Option<RouteFilter>>,
+}
+
+impl<K: Copy + Eq + Hash> Subscribers<K> {
+    pub(crate) fn new() -> Self {
+        Subscribers {
+            filters: HashMap::new(),
+        }
+    }
+
+    /// Adds a subscriber, which is sent every route change until it gets a filter.
+    pub(crate) fn subscribe(&mut self, id: K) {
+        self.filters.insert(id, None);
+    }
+
+    /// Removes a subscriber.
+    pub(crate) fn unsubscribe(&mut self, id: K) {
+        self.filters.remove(&id);
+    }
+
+    /// Sets the filter of a subscriber, if it hasn't been removed.
+    pub(crate) fn set_filter(&mut self, id: K, filter: Option<RouteFilter>) {
+        if let Some(subscriber_filter) = self.filters.get_mut(&id) {
+            *subscriber_filter = filter;
+        }
+    }
+
+    /// Gets the subscribers whose filters the change passes.
+    pub(crate) fn recipients<T>(&self, change: &RouteChange<T>) -> Vec<K> {
+        self.filters
+            .iter()
+            .filter(|(_, filter)| {
+                filter
+                    .as_ref()
+                    .map_or(true, |filter| filter.is_relevant(change))
+            })
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Gets the number of subscribers.
+    pub(crate) fn len(&self) -> usize {
+        self.filters.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::agent::RouteChangeCause;
+    use crate::matcher::RouteMatcher;
+    use crate::route::Route;
+
+    fn change(previous: &str, current: &str) -> RouteChange<()> {
+        RouteChange {
+            previous: Some(Route::from(previous)),
+            current: Route::from(current),
+            cause: RouteChangeCause::Push,
+        }
+    }
+
+    fn recipients(subscribers: &Subscribers<usize>, change: &RouteChange<()>) -> Vec<usize> {
+        let mut recipients = subscribers.recipients(change);
+        recipients.sort();
+        recipients
+    }
+
+    #[test]
+    fn unsubscribed_are_not_sent_changes() {
+        let mut subscribers = Subscribers::new();
+        subscribers.subscribe(1);
+        subscribers.subscribe(2);
+        assert_eq!(recipients(&subscribers, &change("/", "/about")), vec![1, 2]);
+
+        subscribers.unsubscribe(1);
+        assert_eq!(recipients(&subscribers, &change("/about", "/")), vec![2]);
+        assert_eq!(subscribers.len(), 1);
+    }
+
+    #[test]
+    fn filters_do_not_subscribe_again() {
+        let mut subscribers = Subscribers::new();
+        subscribers.subscribe(1);
+        subscribers.unsubscribe(1);
+        subscribers.set_filter(1, None);
+        assert!(recipients(&subscribers, &change("/", "/about")).is_empty());
+    }
+
+    #[test]
+    fn filtered_are_only_sent_relevant_changes() {
+        let mut subscribers = Subscribers::new();
+        subscribers.subscribe(1);
+        subscribers.subscribe(2);
+        let matcher = RouteMatcher::try_from("/users/{id}").expect("should parse");
+        subscribers.set_filter(1, Some(RouteFilter::from(matcher)));
+        assert_eq!(recipients(&subscribers, &change("/", "/about")), vec![2]);
+        assert_eq!(recipients(&subscribers, &change("/", "/users/12")), vec![1, 2]);
+    }
+}
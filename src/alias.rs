@@ -32,6 +32,7 @@ macro_rules! define_router_state {
             #[doc = ">](route/struct.Route.html)."]
             pub type Route = $crate::route::Route<$StateT>;
 
+            #[cfg(feature="service")]
             #[doc = "Alias to [RouteService<"]
             #[doc = $StateName]
             #[doc = ">](route_service/struct.RouteService.html)."]
@@ -43,6 +44,11 @@ macro_rules! define_router_state {
             #[doc = ">](agent/struct.RouteAgent.html)."]
             pub type RouteAgent = $crate::agent::RouteAgent<$StateT>;
 
+            #[doc = "Alias to [RouteChange<"]
+            #[doc = $StateName]
+            #[doc = ">](route/struct.RouteChange.html)."]
+            pub type RouteChange = $crate::route::RouteChange<$StateT>;
+
             #[cfg(feature="agent")]
             #[doc = "Alias to [RouteAgentBridge<"]
             #[doc = $StateName]
@@ -71,7 +77,7 @@ macro_rules! define_router_state {
             #[doc = "Alias to [Router<"]
             #[doc = $StateName]
             #[doc = ">](router/router/struct.Router.html)."]
-            pub type Router<SW, M> = $crate::router::Router<$StateT, SW, M>;
+            pub type Router<SW, M, D = ()> = $crate::router::Router<$StateT, SW, M, D>;
 
         }
     }
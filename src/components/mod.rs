@@ -1,4 +1,6 @@
 //! Components that integrate with the [route agent](struct.RouteAgent.html).
+//!
+//! They can be rendered without the "agent" feature, but then they can't change the route.
 
 mod router_button;
 mod router_link;
@@ -1,5 +1,7 @@
 //! A component wrapping a `<button>` tag that changes the route.
+#[cfg(feature = "agent")]
 use crate::agent::{RouteAgentDispatcher, RouteRequest};
+#[cfg(feature = "agent")]
 use crate::route::Route;
 use yew::prelude::*;
 
@@ -8,8 +10,12 @@ use super::Props;
 use crate::RouterState;
 
 /// Changes the route when clicked.
+///
+/// Without the "agent" feature, there is no `RouteAgent` to change the route,
+/// so clicking it does nothing.
 #[derive(Debug)]
 pub struct RouterButton<T: for<'de> RouterState<'de>> {
+    #[cfg(feature = "agent")]
     router: RouteAgentDispatcher<T>,
     props:  Props<T>,
 }
@@ -19,18 +25,24 @@ impl<T: for<'de> RouterState<'de>> Component for RouterButton<T> {
     type Properties = Props<T>;
 
     fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
-        let router = RouteAgentDispatcher::new();
-        RouterButton { router, props }
+        RouterButton {
+            #[cfg(feature = "agent")]
+            router: RouteAgentDispatcher::new(),
+            props,
+        }
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
         match msg {
             Msg::Clicked => {
-                let route = Route {
-                    route: self.props.link.clone(),
-                    state: self.props.state.clone(),
-                };
-                self.router.send(RouteRequest::ChangeRoute(route));
+                #[cfg(feature = "agent")]
+                {
+                    let route = Route {
+                        route: self.props.link.clone(),
+                        state: self.props.state.clone(),
+                    };
+                    self.router.send(RouteRequest::ChangeRoute(route));
+                }
                 false
             }
         }
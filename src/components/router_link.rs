@@ -1,6 +1,10 @@
 //! A component wrapping an `<a>` tag that changes the route.
+#[cfg(feature = "agent")]
 use crate::agent::{RouteAgentDispatcher, RouteRequest};
+#[cfg(feature = "agent")]
 use crate::route::Route;
+#[cfg(feature = "service")]
+use crate::service::{base_path, prepend_base_path};
 use yew::prelude::*;
 
 use super::Msg;
@@ -8,10 +12,19 @@ use super::Props;
 use crate::RouterState;
 
 /// An anchor tag Component that when clicked, will navigate to the provided route.
+///
+/// The base path is prepended to the `href` of the anchor,
+/// so the link can be opened in a new tab.
+///
+/// Without the "agent" feature, clicking the link lets the browser navigate to the `href`,
+/// and without the "service" feature, no base path is prepended to it.
 #[derive(Debug)]
 pub struct RouterLink<T: for<'de> RouterState<'de>> {
-    router: RouteAgentDispatcher<T>,
-    props:  Props<T>,
+    #[cfg(feature = "agent")]
+    router:    RouteAgentDispatcher<T>,
+    props:     Props<T>,
+    #[cfg(feature = "service")]
+    base_path: String,
 }
 
 impl<T: for<'de> RouterState<'de>> Component for RouterLink<T> {
@@ -19,18 +32,26 @@ impl<T: for<'de> RouterState<'de>> Component for RouterLink<T> {
     type Properties = Props<T>;
 
     fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
-        let router = RouteAgentDispatcher::new();
-        RouterLink { router, props }
+        RouterLink {
+            #[cfg(feature = "agent")]
+            router: RouteAgentDispatcher::new(),
+            props,
+            #[cfg(feature = "service")]
+            base_path: base_path(),
+        }
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
         match msg {
             Msg::Clicked => {
-                let route = Route {
-                    route: self.props.link.clone(),
-                    state: self.props.state.clone(),
-                };
-                self.router.send(RouteRequest::ChangeRoute(route));
+                #[cfg(feature = "agent")]
+                {
+                    let route = Route {
+                        route: self.props.link.clone(),
+                        state: self.props.state.clone(),
+                    };
+                    self.router.send(RouteRequest::ChangeRoute(route));
+                }
                 false
             }
         }
@@ -42,14 +63,13 @@ impl<T: for<'de> RouterState<'de>> Component for RouterLink<T> {
     }
 
     fn view(&self) -> Html<Self> {
-        use stdweb::web::event::IEvent;
-        let target: &str = &self.props.link;
+        let target: &str = &self.href();
 
         html! {
             <a
                 class=self.props.classes.clone(),
                 onclick=|event | {
-                    event.prevent_default();
+                    prevent_navigation(event);
                     Msg::Clicked
                 },
                 disabled=self.props.disabled,
@@ -60,3 +80,26 @@ impl<T: for<'de> RouterState<'de>> Component for RouterLink<T> {
         }
     }
 }
+
+impl<T: for<'de> RouterState<'de>> RouterLink<T> {
+    /// Gets the `href` of the anchor.
+    #[cfg(feature = "service")]
+    fn href(&self) -> String {
+        prepend_base_path(&self.base_path, &self.props.link)
+    }
+
+    /// Gets the `href` of the anchor, which is the link, as there is no base path to prepend.
+    #[cfg(not(feature = "service"))]
+    fn href(&self) -> String {
+        self.props.link.clone()
+    }
+}
+
+/// Stops the browser from navigating to the `href`, because the `RouteAgent` changes the route.
+#[cfg(feature = "agent")]
+fn prevent_navigation<E: stdweb::web::event::IEvent>(event: E) {
+    event.prevent_default();
+}
+
+#[cfg(not(feature = "agent"))]
+fn prevent_navigation<E>(_event: E) {}
@@ -0,0 +1,214 @@
+//! Configuration of routing for the whole application.
+use crate::route::{Route, StateError, VersionedState};
+use crate::service::{normalize_base_path, RouteService};
+use crate::Switch;
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// How the `RouteAgent` scrolls the page when the route changes.
+///
+/// By default, the agent doesn't scroll the page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ScrollBehavior {
+    /// Saves the scroll position in the history state before changing the route,
+    /// and restores it when the browser navigates back or forward to that route.
+    ///
+    /// Changing to a new route scrolls to the top of the page.
+    pub restore_position: bool,
+    /// Scrolls the element whose id matches the fragment, like `#section`, into view
+    /// after the route is changed.
+    pub anchor_fragment: bool,
+}
+
+/// Gets the title of a route, which doesn't include the base path.
+pub type TitleFn = fn(&str) -> Option<String>;
+
+/// Handles a state that couldn't be converted to the route state.
+pub type StateErrorFn = fn(&StateError);
+
+/// Enables versioning on a `RouteService`.
+pub(crate) type ConfigureService<T> = fn(&mut RouteService<T>);
+
+thread_local! {
+    /// The configuration that was installed by `RouterConfig::install`.
+    static INSTALLED: RefCell<RouterConfig> = RefCell::new(RouterConfig::default());
+}
+
+/// The settings that routing components and agents are created with.
+///
+/// Routing components and agents read the configuration when they are created,
+/// so it has to be installed before any of them are created.
+/// Without an installed configuration, the default one is used.
+///
+/// # Example
+/// ```
+///# use yew_router::Switch;
+///# use yew_router::config::{RouterConfig, ScrollBehavior};
+/// #[derive(Switch)]
+/// enum AppRoute {
+///     #[to = "/users/{id}"]
+///     #[title = "Profile {id}"]
+///     Profile { id: usize },
+/// }
+///
+/// RouterConfig::new()
+///     .base_path("/app/v2")
+///     .titles::<AppRoute>()
+///     .scroll_behavior(ScrollBehavior {
+///         restore_position: true,
+///         anchor_fragment: true,
+///     })
+///     .install();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RouterConfig {
+    pub(crate) base_path: Option<String>,
+    pub(crate) scroll_behavior: ScrollBehavior,
+    pub(crate) title_fn: Option<TitleFn>,
+    pub(crate) state_persistence: bool,
+    /// The functions that enable versioning on route services, keyed by their state types.
+    state_versioning: HashMap<TypeId, Rc<dyn Any>>,
+    pub(crate) state_error_fn: Option<StateErrorFn>,
+    pub(crate) link_interception: bool,
+}
+
+impl RouterConfig {
+    /// Creates the default configuration.
+    pub fn new() -> Self {
+        RouterConfig::default()
+    }
+
+    /// Installs the configuration, replacing the one that was installed before.
+    pub fn install(self) {
+        INSTALLED.with(|installed| *installed.borrow_mut() = self);
+    }
+
+    /// Calls `f` with the installed configuration, without cloning it.
+    pub(crate) fn with_installed<R>(f: impl FnOnce(&RouterConfig) -> R) -> R {
+        INSTALLED.with(|installed| f(&installed.borrow()))
+    }
+
+    /// Sets the path that the application is served under, like `/app/v2`.
+    ///
+    /// It is stripped from the routes gotten from the browser, and prepended to the routes that
+    /// are set.
+    /// If it isn't set, the path of the document's `<base href>` element is used.
+    pub fn base_path(mut self, base_path: &str) -> Self {
+        self.base_path = Some(normalize_base_path(base_path));
+        self
+    }
+
+    /// Sets how the `RouteAgent` scrolls the page when the route changes.
+    pub fn scroll_behavior(mut self, scroll_behavior: ScrollBehavior) -> Self {
+        self.scroll_behavior = scroll_behavior;
+        self
+    }
+
+    /// Sets the function that the `RouteAgent` uses to get the titles of routes.
+    ///
+    /// When the route changes, the agent sets the document's title to the title of the new route,
+    /// and provides it to the History API, so the browser's history shows meaningful entries.
+    pub fn title_fn(mut self, title_fn: TitleFn) -> Self {
+        self.title_fn = Some(title_fn);
+        self
+    }
+
+    /// Sets the titles of routes to the titles of the `Switch` they produce,
+    /// which are provided by `#[title]` attributes when it is derived.
+    pub fn titles<SW: Switch>(self) -> Self {
+        self.title_fn(switch_title::<SW>)
+    }
+
+    /// Sets whether the `RouteAgent` persists the state of routes in the `sessionStorage`.
+    ///
+    /// The state of a history entry is kept by the browser,
+    /// but it can't always be converted back to the route state after the page is reloaded.
+    /// When state persistence is enabled, the agent also serializes the route state into the
+    /// `sessionStorage`, keyed by the history entry, and deserializes it from there when the
+    /// current route is requested, or the browser navigates back or forward.
+    pub fn state_persistence(mut self, enabled: bool) -> Self {
+        self.state_persistence = enabled;
+        self
+    }
+
+    /// Makes `RouteAgent`s with the state type `T` store states with its version,
+    /// and migrate states that were stored with older versions.
+    pub fn state_versioning<T: VersionedState>(mut self) -> Self {
+        let configure: ConfigureService<T> = RouteService::set_state_versioning;
+        self.state_versioning
+            .insert(TypeId::of::<T>(), Rc::new(configure));
+        self
+    }
+
+    /// Sets the function that the `RouteAgent` calls when the state of a history entry that the
+    /// browser navigated back or forward to can't be converted to the route state.
+    ///
+    /// The route is still changed, with the default state.
+    pub fn state_error_fn(mut self, state_error_fn: StateErrorFn) -> Self {
+        self.state_error_fn = Some(state_error_fn);
+        self
+    }
+
+    /// Sets whether the `RouteAgent` intercepts clicks on ordinary links, like those in rendered
+    /// markdown, and changes the route to them instead of letting the browser reload the page.
+    ///
+    /// Only links to routes within the base path are intercepted.
+    /// Links can opt out with the `data-router-ignore` attribute,
+    /// and clicks with modifier keys, or on links with a `target`, aren't intercepted.
+    pub fn link_interception(mut self, enabled: bool) -> Self {
+        self.link_interception = enabled;
+        self
+    }
+
+    /// Gets the function that enables versioning on route services with the state type `T`,
+    /// if versioning was enabled for it.
+    pub(crate) fn configure_versioning<T: 'static>(&self) -> Option<ConfigureService<T>> {
+        self.state_versioning
+            .get(&TypeId::of::<T>())
+            .and_then(|configure| configure.downcast_ref::<ConfigureService<T>>())
+            .copied()
+    }
+}
+
+fn switch_title<SW: Switch>(route: &str) -> Option<String> {
+    SW::switch(Route::<()>::from(route)).and_then(|switch| switch.title())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use stdweb::Value;
+
+    #[test]
+    fn base_path_is_normalized() {
+        let config = RouterConfig::new().base_path("app/v2/");
+        assert_eq!(config.base_path, Some("/app/v2".to_string()));
+        assert_eq!(RouterConfig::new().base_path, None);
+    }
+
+    impl VersionedState for String {
+        const VERSION: u32 = 1;
+
+        fn migrate(version: u32, _state: Value) -> Result<Self, StateError> {
+            Err(StateError::UnknownVersion(version))
+        }
+    }
+
+    #[test]
+    fn versioning_is_only_configured_for_its_state_type() {
+        let config = RouterConfig::new().state_versioning::<String>();
+        assert!(config.configure_versioning::<String>().is_some());
+        assert!(config.configure_versioning::<()>().is_none());
+    }
+
+    #[test]
+    fn installed_config_is_read() {
+        RouterConfig::new().base_path("/app").install();
+        let base_path = RouterConfig::with_installed(|config| config.base_path.clone());
+        assert_eq!(base_path, Some("/app".to_string()));
+        RouterConfig::new().install();
+        assert!(RouterConfig::with_installed(|config| config.base_path.is_none()));
+    }
+}
@@ -25,18 +25,31 @@
 //! If you specify your own router_state aliases and functions, you will want to disable the
 //! `unit_alias` feature to prevent the default `()` aliases from showing up in the prelude.
 //!
+//! ## Base Path
+//! If the application is served under a path, like `/app/v2/`, that path can be set by the
+//! `RouterConfig`, or read from the document's `<base href>` element.
+//! It is then stripped from routes before they are matched, so matchers don't need to include it,
+//! and it is prepended to the routes that are navigated to and to the `href`s of `RouterLink`s.
+//!
 //! ## Features
 //! This crate has a bunch of feature-flags.
 //! * "default" - Everything is included by default.
-//! * "core" - The fully feature complete ("router", "components", "matchers"), but without unit_alias.
+//! * "core" - The fully feature complete ("router", "components", "agent", "matchers"), but without unit_alias.
 //! * "unit_alias" - If enabled, a module will be added to the route and expanded within the prelude
 //! for aliases of Router<T> types to their `()` variants. This is useful if want state
-//! * "router" - If enabled, the Router component and its dependent infrastructure (including "agent") will be included.
-//! * "agent" - If enabled, the RouteAgent and its associated types will be included.
+//! * "router" - If enabled, the Router component and its dependent infrastructure will be included.
+//! Without "agent", it only renders the route that it is given, rather than the browser's route.
+//! * "agent" - If enabled, the RouteAgent and its associated types (including "service" and "serde") will be included.
+//! * "service" - If enabled, the RouteService, which interacts with the browser, will be included,
+//! along with the `stdweb` dependency.
+//! Without it, the crate doesn't need a browser, so `Switch`, the matchers, and the `Router` with
+//! a provided route can be used natively, like when prerendering pages on a server.
 //! * "components" - If enabled, the accessory components will be made available.
+//! Without "agent", they can't change the route.
 //! * "matchers" - If enabled, the full matcher suite will be available.
 //! * "regex_matcher" - If enabled, the regex matcher will be available. This can be disabled to avoid including the Regex package.
 //! * "route_matcher" - If enabled, the RouteMatcher will be available.
+//! * "serde" - If enabled, the RouteMatcher and its tokens can be serialized and deserialized.
 
 #![deny(
     missing_docs,
@@ -56,8 +69,14 @@ pub use yew_router_route_parser;
 
 #[macro_use]
 mod alias;
+#[cfg(feature = "service")]
 pub mod service;
 
+#[cfg(feature = "service")]
+pub mod config;
+#[cfg(feature = "service")]
+pub use config::RouterConfig;
+
 #[cfg(feature = "agent")]
 pub mod agent;
 
@@ -86,9 +105,7 @@ pub mod prelude {
     // State restrictions
     #[cfg(feature = "agent")]
     pub use crate::agent::AgentState;
-    pub use crate::route::RouteState;
-    #[cfg(feature = "router")]
-    pub use crate::router::RouterState;
+    pub use crate::route::{RouteState, RouterState};
 }
 
 pub use alias::*;
@@ -99,12 +116,12 @@ pub use matcher::{Captures};
 
 #[cfg(feature = "agent")]
 pub use crate::agent::AgentState;
-pub use crate::route::RouteState;
-#[cfg(feature = "router")]
-pub use crate::router::RouterState;
+pub use crate::route::{RouteState, RouterState};
 
 mod switch;
-pub use switch::Switch;
+pub use switch::{Switch, SwitchDescription, SwitchReport};
+
+pub mod route_table;
 pub use yew_router_macro::Switch;
 
 /// The route macro produces a Matcher which can be used to determine if a route string should cause
@@ -122,12 +139,18 @@ pub use yew_router_macro::Switch;
 /// * If `//` is a possible valid match sequence (this has implications for where Optional sections may be used)
 /// * There can only be one `?` character. It denotes the start of the query section. Subsequent queries must begin with `&`.
 /// * Incomplete queries are not allowed. They must follow the form of `<?|&><literal>=<literal|any>`.
+/// * Characters that have special meaning (`(`, `)`, `|`, `[`, `]`, `{`, `}`, `=`, `&`, `?`, `#`, ` `, and `\`)
+///   must be escaped with a `\` in order to be matched literally, e.g. `/files/report\(1\).pdf`.
 ///
 /// ### Any Matching
 /// On top of just matching strings literally, Any sections, denoted by `{}` can be supplied to
-/// match anything in that section. They must match one or more characters to be valid.
+/// match anything in that section. They must match one or more characters to be valid,
+/// except for `{}`, `{*}` and `{*:key}` at the end of the route, which match nothing if nothing
+/// is left, so `/users{*:rest}` matches `/users` with an empty `rest`.
 /// Captured values will be available as part of a `Captures` struct if the matching succeeds.
 /// `Captures` is a type alias to `HashMap<&str, String>`.
+/// Captured values are percent-decoded, so `/tag/c%2B%2B` captures `c++`.
+/// This can be disabled using the `decode_captures` field of `MatcherSettings`.
 /// If you want to specify that a captured section must be a number, you must capture the string,
 /// and then attempt to parse it to your desired numeric type in `from_matches` or `render`.
 ///
@@ -137,9 +160,15 @@ pub use yew_router_macro::Switch;
 /// * `{key}`- Matches anything, just as above, but stores the captured characters as a String inside a HashMap with the specified name acting as a key.
 /// * `{*}` - Ignore all path separators (`/`), consuming characters until the end of the route or the next section is exactly matched.
 /// * `{*:key}` - Matches as above, but stores the captured characters as a String inside a HashMap.
+///   Each of its sections is percent-decoded on its own, so encoded `/`s (`%2F`) stay encoded.
 /// * `{4}` - Consume the specified number of path separators (`/`) before being allowed to match against a terminating set of characters.
 /// * `{4:key} - Same as above, but stores the captured characters as a String inside a HashMap.
 ///
+/// If a route could be divided between the sections of a matcher in more than one way,
+/// captures end at the first exact section that allows the rest of the route to match,
+/// so `/{name}-{version}` captures `name = "a"` and `version = "b-c"` from `/a-b-c`.
+/// Captures at the end of the matcher consume as much as they are able to.
+///
 /// There is a rule to remember here:
 /// * Because Any matchers use the subsequent exact section to terminate their search, no Any matchers cannot be next to each other.
 ///   * Optional matchers do not adequately separate Any matchers, because they may not match at all, leaving no exact section to separate the Any matchers.
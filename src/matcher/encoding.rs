@@ -0,0 +1,212 @@
+//! Percent-encoding and decoding of route sections.
+//!
+//! Browsers hand the router percent-encoded paths, queries, and fragments.
+//! Captured values are decoded before they are handed to `Switch` implementations,
+//! and values inserted into routes should be encoded with the functions provided here.
+
+/// Characters that are left alone when encoding.
+///
+/// These are the "unreserved" characters as specified in RFC 3986.
+fn is_unreserved(byte: u8) -> bool {
+    match byte {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => true,
+        _ => false,
+    }
+}
+
+/// Characters that delimit sections of a route.
+///
+/// If these appear percent-encoded within a route, they are not considered equivalent to
+/// their unencoded forms, because the encoding is what prevents them from acting as delimiters.
+pub(crate) fn is_delimiter(byte: u8) -> bool {
+    match byte {
+        b'/' | b'?' | b'#' | b'&' | b'=' => true,
+        _ => false,
+    }
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Decodes a `%XX` sequence at the start of the provided bytes.
+pub(crate) fn decode_escape(bytes: &[u8]) -> Option<u8> {
+    if bytes.len() >= 3 && bytes[0] == b'%' {
+        Some(hex_value(bytes[1])? * 16 + hex_value(bytes[2])?)
+    } else {
+        None
+    }
+}
+
+/// Decodes percent-encoded sequences in the provided string.
+///
+/// Malformed sequences (a `%` not followed by two hexadecimal digits) are left as they are.
+/// If the decoded bytes are not valid UTF-8, `None` is returned.
+///
+/// # Example
+/// ```
+///# use yew_router::matcher::encoding::decode;
+/// assert_eq!(decode("c%2B%2B"), Some("c++".to_string()));
+/// assert_eq!(decode("lorem%20ipsum"), Some("lorem ipsum".to_string()));
+/// assert_eq!(decode("100%"), Some("100%".to_string()));
+/// ```
+pub fn decode(i: &str) -> Option<String> {
+    decode_impl(i, |_| false)
+}
+
+/// Decodes percent-encoded sequences in a value that spans multiple sections of a route.
+///
+/// This behaves like `decode`, except that encoded `/`s (`%2F`) are left encoded,
+/// so they can still be told apart from the `/`s that separate the sections.
+///
+/// # Example
+/// ```
+///# use yew_router::matcher::encoding::decode_sections;
+/// assert_eq!(decode_sections("my%20files/report"), Some("my files/report".to_string()));
+/// assert_eq!(decode_sections("a%2Fb/c"), Some("a%2Fb/c".to_string()));
+/// ```
+pub fn decode_sections(i: &str) -> Option<String> {
+    decode_impl(i, |byte| byte == b'/')
+}
+
+/// Decodes every percent-encoded sequence, unless the byte it encodes is kept encoded.
+fn decode_impl(i: &str, keep: fn(u8) -> bool) -> Option<String> {
+    let bytes = i.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match decode_escape(&bytes[index..]) {
+            Some(byte) if !keep(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            _ => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Percent-encodes every byte that isn't unreserved, unless it is kept.
+fn encode_impl(i: &str, keep: fn(u8) -> bool) -> String {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+    let mut encoded = String::with_capacity(i.len());
+    for &byte in i.as_bytes() {
+        if is_unreserved(byte) || keep(byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push('%');
+            encoded.push(HEX[(byte >> 4) as usize] as char);
+            encoded.push(HEX[(byte & 0x0F) as usize] as char);
+        }
+    }
+    encoded
+}
+
+/// Percent-encodes a value so that it can be placed within a single section of a route.
+///
+/// Every character other than the unreserved characters (`A-Z`, `a-z`, `0-9`, `-`, `.`, `_`, `~`)
+/// is encoded, including `/`.
+///
+/// # Example
+/// ```
+///# use yew_router::matcher::encoding::encode_section;
+/// assert_eq!(encode_section("c++"), "c%2B%2B");
+/// assert_eq!(encode_section("a/b"), "a%2Fb");
+/// ```
+pub fn encode_section(i: &str) -> String {
+    encode_impl(i, |_| false)
+}
+
+/// Percent-encodes a value that spans multiple sections of a route.
+///
+/// This behaves like `encode_section`, except that `/` is left as is.
+///
+/// # Example
+/// ```
+///# use yew_router::matcher::encoding::encode_sections;
+/// assert_eq!(encode_sections("my files/report 1.pdf"), "my%20files/report%201.pdf");
+/// ```
+pub fn encode_sections(i: &str) -> String {
+    encode_impl(i, |byte| byte == b'/')
+}
+
+/// Percent-encodes the exact text of a matcher, so it can be placed within a route.
+///
+/// The delimiters are left as is, because they delimit the sections of the route.
+pub(crate) fn encode_exact(i: &str) -> String {
+    encode_impl(i, is_delimiter)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode_plain() {
+        assert_eq!(decode("lorem"), Some("lorem".to_string()));
+    }
+
+    #[test]
+    fn decode_escapes() {
+        assert_eq!(decode("c%2B%2B"), Some("c++".to_string()));
+        assert_eq!(decode("c%2b%2b"), Some("c++".to_string()));
+    }
+
+    #[test]
+    fn decode_multibyte() {
+        assert_eq!(decode("caf%C3%A9"), Some("café".to_string()));
+    }
+
+    #[test]
+    fn decode_leaves_malformed_escapes() {
+        assert_eq!(decode("%"), Some("%".to_string()));
+        assert_eq!(decode("%2"), Some("%2".to_string()));
+        assert_eq!(decode("%zz"), Some("%zz".to_string()));
+    }
+
+    #[test]
+    fn decode_rejects_invalid_utf8() {
+        assert_eq!(decode("%FF"), None);
+    }
+
+    #[test]
+    fn encode_section_encodes_slash() {
+        assert_eq!(encode_section("a/b c"), "a%2Fb%20c");
+    }
+
+    #[test]
+    fn encode_sections_keeps_slash() {
+        assert_eq!(encode_sections("a/b c"), "a/b%20c");
+    }
+
+    #[test]
+    fn encode_exact_keeps_delimiters() {
+        assert_eq!(encode_exact("/a b?c=d&e#f"), "/a%20b?c=d&e#f");
+    }
+
+    #[test]
+    fn encode_multibyte() {
+        assert_eq!(encode_section("café"), "caf%C3%A9");
+    }
+
+    #[test]
+    fn decode_sections_keeps_encoded_slash() {
+        assert_eq!(decode_sections("a%2fb/c%20d"), Some("a%2fb/c d".to_string()));
+    }
+
+    #[test]
+    fn encode_decode_round_trip() {
+        let value = "lorem ipsum/dolor?sit=amet&c++#é";
+        assert_eq!(decode(&encode_section(value)), Some(value.to_string()));
+        assert_eq!(decode(&encode_sections(value)), Some(value.to_string()));
+        assert_eq!(decode_sections(&encode_sections(value)), Some(value.to_string()));
+    }
+}
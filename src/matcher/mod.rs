@@ -4,6 +4,10 @@ pub use yew_router_route_parser::{
     parser::YewRouterParseError, Capture, CaptureVariant, Captures, MatcherToken,
 };
 
-
+pub mod encoding;
 mod route_matcher;
-pub use self::route_matcher::{MatcherSettings, RouteMatcher};
+mod route_set;
+pub use self::route_matcher::{
+    MatchReport, MatchStep, MatcherSettings, RouteMatcher, Specificity, StepOutcome,
+};
+pub use self::route_set::{RouteSet, SelectionMode};
@@ -1,23 +1,38 @@
+//! Matching of route strings against `MatcherToken`s.
+//!
+//! Captures can match sections of varying length, so matching backtracks:
+//! when the tokens after a capture fail to match, the capture is retried with a different length,
+//! and when the tokens after an optional section fail to match, the section is retried as skipped.
+use crate::matcher::encoding;
+use crate::matcher::route_matcher::report::{Recorder, StepOutcome};
 use crate::matcher::route_matcher::util::tag_possibly_case_sensitive;
 use crate::matcher::route_matcher::MatcherSettings;
 use crate::matcher::Captures;
 use log::{debug, trace};
-use nom::branch::alt;
-use nom::bytes::complete::{is_not, tag};
-use nom::combinator::{map, opt, verify};
 use nom::error::ErrorKind;
-use nom::sequence::terminated;
 use nom::IResult;
-use std::iter::Peekable;
-use std::slice::Iter;
-use yew_router_route_parser::parser::util::consume_until;
-use yew_router_route_parser::{CaptureVariant, MatcherToken};
+use std::ops::Range;
+use yew_router_route_parser::{Capture, CaptureVariant, MatcherToken};
 
-/// Allows abstracting over capturing into a HashMap (Captures) or a Vec.
+/// Characters that can't be captured by captures that match a single path section.
+const INVALID_CAPTURE_CHARACTERS: &str = " */#&?{}=";
+/// Characters that can't be captured by `{*}` captures.
+const INVALID_MANY_CAPTURE_CHARACTERS: &str = " #&?=";
+
+/// Percent-decodes captured text, returning `None` if the decoded text isn't valid utf-8.
+pub type Decode = fn(&str) -> Option<String>;
+
+/// Allows abstracting over capturing into a HashMap (Captures), a Vec, or a Vec of spans.
+///
+/// The matcher only finds the spans of the route that were captured,
+/// so collections that hold strings have to copy them out of the route.
 pub trait CaptureCollection<'a> {
     fn new2() -> Self;
-    fn insert2(&mut self, key: &'a str, value: String);
-    fn extend2(&mut self, other: Self);
+    /// Adds the capture that spans the given bytes of the route.
+    ///
+    /// `decode` percent-decodes the captured text, if the collection holds the text
+    /// and it should be decoded.
+    fn insert2(&mut self, key: &'a str, route: &str, span: Range<usize>, decode: Option<Decode>);
 }
 
 impl<'a> CaptureCollection<'a> for Captures<'a> {
@@ -25,12 +40,8 @@ impl<'a> CaptureCollection<'a> for Captures<'a> {
         Captures::new()
     }
 
-    fn insert2(&mut self, key: &'a str, value: String) {
-        self.insert(key, value);
-    }
-
-    fn extend2(&mut self, other: Self) {
-        self.extend(other)
+    fn insert2(&mut self, key: &'a str, route: &str, span: Range<usize>, decode: Option<Decode>) {
+        self.insert(key, captured_text(route, span, decode));
     }
 }
 
@@ -39,12 +50,18 @@ impl<'a> CaptureCollection<'a> for Vec<(&'a str, String)> {
         Vec::new()
     }
 
-    fn insert2(&mut self, key: &'a str, value: String) {
-        self.push((key, value))
+    fn insert2(&mut self, key: &'a str, route: &str, span: Range<usize>, decode: Option<Decode>) {
+        self.push((key, captured_text(route, span, decode)))
+    }
+}
+
+impl<'a> CaptureCollection<'a> for Vec<(&'a str, Range<usize>)> {
+    fn new2() -> Self {
+        Vec::new()
     }
 
-    fn extend2(&mut self, other: Self) {
-        self.extend(other)
+    fn insert2(&mut self, key: &'a str, _route: &str, span: Range<usize>, _decode: Option<Decode>) {
+        self.push((key, span))
     }
 }
 
@@ -53,7 +70,7 @@ pub(super) fn match_path<'a, 'b: 'a>(
     tokens: &'b [MatcherToken],
     settings: &'b MatcherSettings,
 ) -> impl Fn(&'a str) -> IResult<&'a str, Captures<'b>> {
-    move |i: &str| match_path_impl(tokens, *settings, i)
+    move |i: &str| match_path_recorded(tokens, *settings, i, &mut Recorder::disabled())
 }
 
 #[allow(clippy::trivially_copy_pass_by_ref)]
@@ -61,320 +78,381 @@ pub(super) fn match_path_list<'a, 'b: 'a>(
     tokens: &'b [MatcherToken],
     settings: &'b MatcherSettings,
 ) -> impl Fn(&'a str) -> IResult<&'a str, Vec<(&'b str, String)>> {
-    move |i: &str| match_path_impl(tokens, *settings, i)
+    move |i: &str| match_path_recorded(tokens, *settings, i, &mut Recorder::disabled())
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+pub(super) fn match_path_spans<'a, 'b: 'a>(
+    tokens: &'b [MatcherToken],
+    settings: &'b MatcherSettings,
+) -> impl Fn(&'a str) -> IResult<&'a str, Vec<(&'b str, Range<usize>)>> {
+    move |i: &str| match_path_recorded(tokens, *settings, i, &mut Recorder::disabled())
 }
 
-fn match_path_impl<'a, 'b: 'a, CAP: CaptureCollection<'b>>(
+/// Matches the path, recording the steps taken if the recorder is enabled.
+///
+/// If `settings.complete` is set, the whole path must be matched,
+/// otherwise the first match found for a prefix of the path is accepted.
+pub(super) fn match_path_recorded<'a, 'b: 'a, CAP: CaptureCollection<'b>>(
     tokens: &'b [MatcherToken],
     settings: MatcherSettings,
-    mut i: &'a str,
+    i: &'a str,
+    recorder: &mut Recorder,
 ) -> IResult<&'a str, CAP> {
     trace!("Attempting to match path: {:?} using: {:?}", i, tokens);
-
-    let mut iter = tokens.iter().peekable();
-
-    let mut captures: CAP = CAP::new2();
-
-    while let Some(token) = iter.next() {
-        i = match token {
-            MatcherToken::Exact(literal) => {
-                trace!("Matching '{}' against literal: '{}'", i, literal);
-                tag_possibly_case_sensitive(
-                    literal.as_str(),
-                    !settings.case_insensitive,
-                )(i)?
-                .0
-            }
-            MatcherToken::Optional(inner_tokens) => {
-                match opt(|i| match_path_impl(&inner_tokens, settings, i))(i) {
-                    Ok((ii, inner_captures)) => {
-                        if let Some(inner_captures) = inner_captures {
-                            captures.extend2(inner_captures);
-                        }
-                        ii
+    let mut matcher = Matcher {
+        route: i,
+        settings,
+        captures: vec![],
+        recorder,
+    };
+    match matcher.match_tokens(tokens, 0, None, 0) {
+        Some(end) => {
+            debug!("Path Matched");
+            let mut captures = CAP::new2();
+            let decoding = |decode: Decode| Some(decode).filter(|_| settings.decode_captures);
+            for (capture, span) in matcher.captures {
+                match &capture.capture_variant {
+                    CaptureVariant::Named(name) | CaptureVariant::NumberedNamed { name, .. } => {
+                        captures.insert2(name, i, span, decoding(encoding::decode))
+                    }
+                    CaptureVariant::ManyNamed(name) => {
+                        captures.insert2(name, i, span, decoding(encoding::decode_sections))
                     }
-                    _ => i, // Do nothing if this fails
+                    CaptureVariant::Unnamed
+                    | CaptureVariant::ManyUnnamed
+                    | CaptureVariant::NumberedUnnamed { .. } => {}
                 }
             }
-            MatcherToken::Capture(capture) => match &capture.capture_variant {
-                CaptureVariant::Unnamed => {
-                    capture_unnamed(i, &mut iter, &capture.allowed_captures)?
-                }
-                CaptureVariant::ManyUnnamed => capture_many_unnamed(
-                    i,
-                    &mut iter,
-                    &capture.allowed_captures,
-                )?,
-                CaptureVariant::NumberedUnnamed { sections } => {
-                    capture_numbered_named::<CAP>(
-                        i,
-                        &mut iter,
-                        None,
-                        *sections,
-                        &capture.allowed_captures,
-                    )?
-                }
-                CaptureVariant::Named(name) => capture_named(
-                    i,
-                    &mut iter,
-                    &name,
-                    &mut captures,
-                    &capture.allowed_captures,
-                )?,
-                CaptureVariant::ManyNamed(name) => capture_many_named(
-                    i,
-                    &mut iter,
-                    &name,
-                    &mut captures,
-                    &capture.allowed_captures,
-                )?,
-                CaptureVariant::NumberedNamed { sections, name } => {
-                    capture_numbered_named(
-                        i,
-                        &mut iter,
-                        Some((&name, &mut captures)),
-                        *sections,
-                        &capture.allowed_captures,
-                    )?
-                }
-            },
-        };
+            Ok((&i[end..], captures))
+        }
+        None => Err(nom::Err::Error((i, ErrorKind::Verify))),
     }
-    debug!("Path Matched");
-
-    Ok((i, captures))
 }
 
-// TODO This section of code is kind of a mess. It needs a pretty through rework.
-
-/// Captures a section and doesn't add it to the matches.
+/// Tokens that remain to be matched after the tokens currently being matched run out.
 ///
-/// It will capture characters until a separator or other invalid character is encountered
-/// and the next string of characters is confirmed to be the next literal.
-pub fn capture_unnamed<'a>(
-    i: &'a str,
-    iter: &mut Peekable<Iter<MatcherToken>>,
-    allowed_captures: &Option<Vec<String>>,
-) -> Result<&'a str, nom::Err<(&'a str, ErrorKind)>> {
-    log::trace!("Matching Unnamed");
-    let ii = if let Some(_peaked_next_token) = iter.peek() {
-        let delimiter = yew_router_route_parser::next_delimiters(iter.clone());
-        let matcher = alt((
-            consume_until(alt((tag("/"), tag("?"), tag("#")))),
-            consume_until(delimiter),
-        ));
-        optionally_check_if_parsed_is_allowed_capture(
-            matcher,
-            allowed_captures,
-        )(i)?
-        .0
-    } else if i.is_empty() {
-        i // Match even if nothing is left
-    } else {
-        let valid_capture_characters =
-            map(valid_capture_characters, String::from);
-        optionally_check_if_parsed_is_allowed_capture(
-            valid_capture_characters,
-            allowed_captures,
-        )(i)?
-        .0
-    };
-    Ok(ii)
+/// Continuations form a stack that mirrors the optional sections being matched.
+struct Continuation<'b, 'c> {
+    tokens: &'b [MatcherToken],
+    depth: usize,
+    /// The step for the optional section that ends where this continuation begins.
+    optional_step: Option<usize>,
+    next: Option<&'c Continuation<'b, 'c>>,
 }
 
-fn capture_many_unnamed<'a>(
-    i: &'a str,
-    iter: &mut Peekable<Iter<MatcherToken>>,
-    allowed_captures: &Option<Vec<String>>,
-) -> Result<&'a str, nom::Err<(&'a str, ErrorKind)>> {
-    trace!("Matching ManyUnnamed");
-    let ii = if let Some(_peaked_next_token) = iter.peek() {
-        let delimiter = yew_router_route_parser::next_delimiters(iter.clone());
-        optionally_check_if_parsed_is_allowed_capture(
-            consume_until(delimiter),
-            allowed_captures,
-        )(i)?
-        .0
-    } else if i.is_empty() {
-        i // Match even if nothing is left
-    } else {
-        let valid_many_capture_characters =
-            map(valid_many_capture_characters, String::from);
-        optionally_check_if_parsed_is_allowed_capture(
-            valid_many_capture_characters,
-            allowed_captures,
-        )(i)?
-        .0
-    };
-    Ok(ii)
+/// The exact sections that may directly follow a capture.
+struct Delimiters<'b> {
+    exacts: Vec<&'b str>,
+    /// Whether nothing may follow the capture, because the tokens after it can all be skipped.
+    may_end: bool,
 }
 
-fn capture_named<'a, 'b: 'a, CAP: CaptureCollection<'b>>(
-    i: &'a str,
-    iter: &mut Peekable<Iter<MatcherToken>>,
-    capture_key: &'b str,
-    matches: &mut CAP,
-    allowed_captures: &Option<Vec<String>>,
-) -> Result<&'a str, nom::Err<(&'a str, ErrorKind)>> {
-    log::trace!("Matching Named ({})", capture_key);
-    if let Some(_peaked_next_token) = iter.peek() {
-        let delimiter = yew_router_route_parser::next_delimiters(iter.clone());
-        let (ii, captured) = optionally_check_if_parsed_is_allowed_capture(
-            consume_until(delimiter),
-            allowed_captures,
-        )(i)?;
-        matches.insert2(capture_key, captured);
-        Ok(ii)
-    } else {
-        let (ii, captured) = optionally_check_if_parsed_is_allowed_capture(
-            map(valid_capture_characters, String::from),
-            allowed_captures,
-        )(i)?;
-        matches.insert2(capture_key, captured.to_string());
-        Ok(ii)
+impl<'b> Delimiters<'b> {
+    /// Finds the exact sections that may follow the tokens.
+    ///
+    /// Returns `None` if a capture may directly follow them.
+    fn following(
+        mut tokens: &'b [MatcherToken],
+        mut continuation: Option<&Continuation<'b, '_>>,
+    ) -> Option<Self> {
+        let mut exacts = vec![];
+        loop {
+            if leading_exacts(tokens, &mut exacts)? {
+                return Some(Delimiters {
+                    exacts,
+                    may_end: false,
+                });
+            }
+            match continuation {
+                Some(next) => {
+                    tokens = next.tokens;
+                    continuation = next.next;
+                }
+                None => {
+                    return Some(Delimiters {
+                        exacts,
+                        may_end: true,
+                    })
+                }
+            }
+        }
     }
 }
 
-fn capture_many_named<'a, 'b, CAP: CaptureCollection<'b>>(
-    i: &'a str,
-    iter: &mut Peekable<Iter<MatcherToken>>,
-    capture_key: &'b str,
-    matches: &mut CAP,
-    allowed_captures: &Option<Vec<String>>,
-) -> Result<&'a str, nom::Err<(&'a str, ErrorKind)>> {
-    log::trace!("Matching NumberedUnnamed ({})", capture_key);
-    if let Some(_peaked_next_token) = iter.peek() {
-        let delimiter = yew_router_route_parser::next_delimiters(iter.clone());
-        let (ii, captured) = optionally_check_if_parsed_is_allowed_capture(
-            consume_until(delimiter),
-            allowed_captures,
-        )(i)?;
-        matches.insert2(&capture_key, captured);
-        Ok(ii)
-    } else if i.is_empty() {
-        matches.insert2(&capture_key, "".to_string()); // TODO Is this a thing I want?
-        Ok(i) // Match even if nothing is left
-    } else {
-        let (ii, c) = optionally_check_if_parsed_is_allowed_capture(
-            map(valid_many_capture_characters, String::from),
-            allowed_captures,
-        )(i)?;
-        matches.insert2(&capture_key, c.to_string());
-        Ok(ii)
+/// Collects the exact sections that the tokens may start with.
+///
+/// Returns whether a mandatory exact section was found,
+/// or `None` if the tokens may start with a capture.
+fn leading_exacts<'b>(tokens: &'b [MatcherToken], exacts: &mut Vec<&'b str>) -> Option<bool> {
+    for token in tokens {
+        match token {
+            MatcherToken::Exact(literal) => {
+                exacts.push(literal);
+                return Some(true);
+            }
+            // The optional section may be skipped, so the tokens after it may come first too.
+            MatcherToken::Optional(inner) => {
+                leading_exacts(inner, exacts)?;
+            }
+            MatcherToken::Capture(_) => return None,
+        }
     }
+    Some(false)
+}
+
+struct Matcher<'a, 'b, 'r> {
+    route: &'a str,
+    settings: MatcherSettings,
+    /// The captures made so far, in order.
+    captures: Vec<(&'b Capture, Range<usize>)>,
+    recorder: &'r mut Recorder,
 }
 
-fn capture_numbered_named<'a, 'b, CAP: CaptureCollection<'b>>(
-    mut i: &'a str,
-    iter: &mut Peekable<Iter<MatcherToken>>,
-    name_and_captures: Option<(&'b str, &mut CAP)>,
-    mut sections: usize,
-    allowed_captures: &Option<Vec<String>>,
-) -> Result<&'a str, nom::Err<(&'a str, ErrorKind)>> {
-    log::trace!("Matching NumberedNamed ({})", sections);
-    let mut captured = "".to_string();
-
-    if let Some(_peaked_next_token) = iter.peek() {
-        while sections > 0 {
-            if sections > 1 {
-                let (ii, c) =
-                    terminated(valid_capture_characters, tag("/"))(i)?;
-                i = ii;
-                captured += c;
-                captured += "/";
-            } else {
-                let delimiter =
-                    yew_router_route_parser::next_delimiters(iter.clone());
-                let (ii, c) = consume_until(delimiter)(i)?;
-                i = ii;
-                captured += &c;
+impl<'a, 'b, 'r> Matcher<'a, 'b, 'r> {
+    /// Matches the tokens, followed by the continuation, starting at the given position.
+    ///
+    /// Returns where the match ended.
+    /// If matching fails, the captures are left as they were.
+    fn match_tokens(
+        &mut self,
+        tokens: &'b [MatcherToken],
+        depth: usize,
+        continuation: Option<&Continuation<'b, '_>>,
+        position: usize,
+    ) -> Option<usize> {
+        let (token, rest) = match tokens.split_first() {
+            Some(split) => split,
+            None => return self.match_continuation(continuation, position),
+        };
+        match token {
+            MatcherToken::Exact(literal) => {
+                trace!(
+                    "Matching '{}' against literal: '{}'",
+                    &self.route[position..],
+                    literal
+                );
+                let step = self.recorder.begin(token, depth, position, vec![]);
+                let remaining = &self.route[position..];
+                match tag_possibly_case_sensitive(literal, !self.settings.case_insensitive)(
+                    remaining,
+                ) {
+                    Ok((after, _)) => {
+                        let end = self.route.len() - after.len();
+                        self.recorder.finish(step, StepOutcome::Matched, end);
+                        self.match_tokens(rest, depth, continuation, end)
+                    }
+                    Err(_) => {
+                        self.recorder.finish(step, StepOutcome::Failed, position);
+                        None
+                    }
+                }
+            }
+            MatcherToken::Optional(inner) => {
+                let step = self.recorder.begin(token, depth, position, vec![]);
+                let after = Continuation {
+                    tokens: rest,
+                    depth,
+                    optional_step: step,
+                    next: continuation,
+                };
+                if let Some(end) = self.match_tokens(inner, depth + 1, Some(&after), position) {
+                    return Some(end);
+                }
+                self.recorder.skip(step, token, depth, position);
+                self.match_tokens(rest, depth, continuation, position)
+            }
+            MatcherToken::Capture(capture) => {
+                self.match_capture(token, capture, rest, depth, continuation, position)
             }
-            sections -= 1;
-            println!("{}", i);
         }
-    } else {
-        while sections > 0 {
-            if sections > 1 {
-                let (ii, c) =
-                    terminated(valid_capture_characters, tag("/"))(i)?;
-                i = ii;
-                captured += c;
-            } else {
-                // Don't consume the next character on the last section
-                let (ii, c) = valid_capture_characters(i)?;
-                i = ii;
-                captured += c;
+    }
+
+    /// Continues matching after the tokens of a section ran out.
+    fn match_continuation(
+        &mut self,
+        continuation: Option<&Continuation<'b, '_>>,
+        position: usize,
+    ) -> Option<usize> {
+        match continuation {
+            Some(continuation) => {
+                self.recorder
+                    .finish(continuation.optional_step, StepOutcome::Matched, position);
+                self.match_tokens(
+                    continuation.tokens,
+                    continuation.depth,
+                    continuation.next,
+                    position,
+                )
             }
-            sections -= 1;
-            println!("{}", i);
+            None if !self.settings.complete || position == self.route.len() => Some(position),
+            None => None,
         }
     }
 
-    if let Some(allowed_captures) = allowed_captures {
-        if allowed_captures.iter().any(|x| x == &captured) {
-            if let Some((name, captures)) = name_and_captures {
-                captures.insert2(&name, captured);
+    /// Tries each length the capture could have, until the tokens after it match.
+    fn match_capture(
+        &mut self,
+        token: &'b MatcherToken,
+        capture: &'b Capture,
+        rest: &'b [MatcherToken],
+        depth: usize,
+        continuation: Option<&Continuation<'b, '_>>,
+        position: usize,
+    ) -> Option<usize> {
+        trace!("Matching capture: {:?}", capture);
+        let delimiters = Delimiters::following(rest, continuation);
+        let recorded_delimiters: Vec<String> = match &delimiters {
+            Some(delimiters) if self.recorder.is_enabled() => delimiters
+                .exacts
+                .iter()
+                .map(|exact| exact.to_string())
+                .collect(),
+            _ => vec![],
+        };
+        let mut ends = capture_ends(&capture.capture_variant, &self.route[position..]);
+        // Captures stop at the first delimiter that lets the rest of the route match,
+        // unless nothing has to follow them, in which case they consume as much as they can.
+        if let Some(Delimiters { may_end: true, .. }) = delimiters {
+            ends.reverse();
+        }
+        let mut attempted = false;
+        for end in ends.into_iter().map(|end| position + end) {
+            if !self.is_delimited(delimiters.as_ref(), end)
+                || !is_allowed_capture(capture, &self.route[position..end])
+            {
+                continue;
             }
-            Ok(i)
-        } else {
-            Err(nom::Err::Error((i, ErrorKind::Verify)))
+            attempted = true;
+            let step = self
+                .recorder
+                .begin(token, depth, position, recorded_delimiters.clone());
+            self.recorder.finish(step, StepOutcome::Matched, end);
+            self.captures.push((capture, position..end));
+            if let Some(end) = self.match_tokens(rest, depth, continuation, end) {
+                return Some(end);
+            }
+            self.captures.pop();
         }
-    } else {
-        if let Some((name, captures)) = name_and_captures {
-            captures.insert2(&name, captured);
+        if !attempted {
+            let step = self
+                .recorder
+                .begin(token, depth, position, recorded_delimiters);
+            self.recorder.finish(step, StepOutcome::Failed, position);
         }
-        Ok(i)
+        None
+    }
+
+    /// Whether a capture ending at `end` would be followed by one of its delimiters.
+    fn is_delimited(&self, delimiters: Option<&Delimiters>, end: usize) -> bool {
+        let delimiters = match delimiters {
+            Some(delimiters) => delimiters,
+            None => return true,
+        };
+        let remaining = &self.route[end..];
+        let ends_route = remaining.is_empty() || !self.settings.complete;
+        (delimiters.may_end && ends_route)
+            || delimiters.exacts.iter().any(|exact| {
+                tag_possibly_case_sensitive(exact, !self.settings.case_insensitive)(remaining)
+                    .is_ok()
+            })
     }
 }
 
-/// Characters that don't interfere with parsing logic for capturing characters
-fn valid_capture_characters(i: &str) -> IResult<&str, &str> {
-    const INVALID_CHARACTERS: &str = " */#&?{}=";
-    is_not(INVALID_CHARACTERS)(i)
+/// The offsets into `i` at which the capture could end, shortest first.
+///
+/// Captures must capture at least one character, except for unnamed captures and captures of
+/// many sections at the end of the route, which capture nothing if nothing is left.
+fn capture_ends(capture_variant: &CaptureVariant, i: &str) -> Vec<usize> {
+    match capture_variant {
+        CaptureVariant::Unnamed | CaptureVariant::ManyUnnamed | CaptureVariant::ManyNamed(_)
+            if i.is_empty() =>
+        {
+            vec![0]
+        }
+        CaptureVariant::Unnamed | CaptureVariant::Named(_) => {
+            run_ends(i, INVALID_CAPTURE_CHARACTERS)
+        }
+        CaptureVariant::ManyUnnamed | CaptureVariant::ManyNamed(_) => {
+            run_ends(i, INVALID_MANY_CAPTURE_CHARACTERS)
+        }
+        CaptureVariant::NumberedUnnamed { sections }
+        | CaptureVariant::NumberedNamed { sections, .. } => numbered_ends(i, *sections),
+    }
 }
 
-fn valid_many_capture_characters(i: &str) -> IResult<&str, &str> {
-    const INVALID_CHARACTERS: &str = " #&?=";
-    is_not(INVALID_CHARACTERS)(i)
+/// The offsets at which a run of one or more characters that aren't `invalid` could end,
+/// shortest first.
+fn run_ends(i: &str, invalid: &str) -> Vec<usize> {
+    let mut ends = vec![];
+    for (index, c) in i.char_indices() {
+        if invalid.contains(c) {
+            break;
+        }
+        ends.push(index + c.len_utf8());
+    }
+    ends
 }
 
-/// If the allowed matches is Some, then check to see if any of the elements in it are what was captured.
-/// It will fail if the captured value was not in the provided list.
-///
-/// If none, it will allow the capture as intended.
-fn optionally_check_if_parsed_is_allowed_capture<'a, F: 'a>(
-    f: F,
-    allowed_captures: &Option<Vec<String>>,
-) -> impl Fn(&'a str) -> IResult<&'a str, String, (&'a str, ErrorKind)>
-//Result<&'a str, nom::Err<(&'a str, ErrorKind)>>
-where
-    F: Fn(&'a str) -> IResult<&'a str, String, (&'a str, ErrorKind)>,
-{
-    let am = allowed_captures.clone(); // I hate how this has to clone in order to work.
-    map(
-        verify(f, move |s: &String| {
-            if let Some(am) = &am {
-                am.iter().map(String::as_str).any(|x| x == s)
-            } else {
-                true
-            }
-        }),
-        |o| o.to_string(),
-    )
+/// The offsets at which a capture of `sections` non-empty path sections could end.
+fn numbered_ends(i: &str, sections: usize) -> Vec<usize> {
+    if sections == 0 {
+        return vec![0];
+    }
+    let mut start = 0;
+    // Every section but the last one is terminated by a separator, so their lengths are fixed.
+    for _ in 1..sections {
+        let length = run_ends(&i[start..], INVALID_CAPTURE_CHARACTERS)
+            .last()
+            .cloned()
+            .unwrap_or_default();
+        if length == 0 || !i[start + length..].starts_with('/') {
+            return vec![];
+        }
+        start += length + 1;
+    }
+    run_ends(&i[start..], INVALID_CAPTURE_CHARACTERS)
+        .into_iter()
+        .map(|end| start + end)
+        .collect()
+}
+
+/// If the capture restricts what it may capture, checks that the captured text is allowed.
+fn is_allowed_capture(capture: &Capture, captured: &str) -> bool {
+    match &capture.allowed_captures {
+        Some(allowed_captures) => allowed_captures.iter().any(|allowed| allowed == captured),
+        None => true,
+    }
 }
 
-//fn valid_capture_characters_in_query(i: &str) -> IResult<&str, &str> {
-//    const INVALID_CHARACTERS: &str = " *#&?|{}=";
-//    is_not(INVALID_CHARACTERS)(i)
-//}
+/// Copies the captured text out of the route, percent-decoding it with `decode` if it is set.
+///
+/// If the decoded text would not be valid utf-8, the text is kept as it was captured.
+fn captured_text(route: &str, span: Range<usize>, decode: Option<Decode>) -> String {
+    let captured = &route[span];
+    decode
+        .and_then(|decode| decode(captured))
+        .unwrap_or_else(|| captured.to_string())
+}
 
 #[cfg(test)]
 mod integration_test {
     use super::*;
 
     use yew_router_route_parser;
-    use yew_router_route_parser::Capture;
-    //    use nom::combinator::all_consuming;
+
+    /// Matches a prefix of the route, like other nom parsers.
+    fn match_path_impl<'a, 'b: 'a>(
+        tokens: &'b [MatcherToken],
+        settings: MatcherSettings,
+        i: &'a str,
+    ) -> IResult<&'a str, Captures<'b>> {
+        let settings = MatcherSettings {
+            complete: false,
+            ..settings
+        };
+        match_path_recorded(tokens, settings, i, &mut Recorder::disabled())
+    }
 
     #[test]
     fn match_query_after_path() {
@@ -753,3 +831,256 @@ mod integration_test {
             .expect_err("should not match");
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A matcher string, a route, and the captures it should produce, or `None` if it shouldn't match.
+    type Case = (
+        &'static str,
+        &'static str,
+        Option<&'static [(&'static str, &'static str)]>,
+    );
+
+    const CASES: &[Case] = &[
+        // Exact sections
+        ("/lorem", "/lorem", Some(&[])),
+        ("/lorem", "/lorem/", Some(&[])),
+        ("/lorem", "/ipsum", None),
+        ("/lorem", "/lorem/ipsum", None),
+        ("/lorem/ipsum", "/lorem", None),
+        // Single section captures
+        ("/{lorem}", "/ipsum", Some(&[("lorem", "ipsum")])),
+        ("/{lorem}", "/", None),
+        ("/{lorem}", "/ipsum/dolor", None),
+        (
+            "/{lorem}/{ipsum}",
+            "/dolor/sit",
+            Some(&[("lorem", "dolor"), ("ipsum", "sit")]),
+        ),
+        ("/lorem/{}", "/lorem/", Some(&[])),
+        ("/{}/lorem", "//lorem", None),
+        ("/lorem/{}", "/lorem/ipsum", Some(&[])),
+        // Captures that contain their own delimiter
+        ("/{a}-{b}", "/x-y", Some(&[("a", "x"), ("b", "y")])),
+        ("/{a}-{b}", "/x-y-z", Some(&[("a", "x"), ("b", "y-z")])),
+        ("/{a}-{b}", "/x", None),
+        ("/{a}-{b}/c", "/x-y-z/c", Some(&[("a", "x"), ("b", "y-z")])),
+        ("/{a}-v{b}", "/x-y-v2", Some(&[("a", "x-y"), ("b", "2")])),
+        (
+            "/{a}.{b}",
+            "/file.tar.gz",
+            Some(&[("a", "file"), ("b", "tar.gz")]),
+        ),
+        ("/{a}-{b}", "/é-ü", Some(&[("a", "é"), ("b", "ü")])),
+        // Many captures
+        ("/lorem/{*}", "/lorem/", Some(&[])),
+        ("/lorem/{*:path}", "/lorem/", Some(&[("path", "")])),
+        ("/lorem{*:path}", "/lorem", Some(&[("path", "")])),
+        ("/{*:path}/edit", "//edit", None),
+        (
+            "/lorem/{*:path}",
+            "/lorem/ipsum/dolor",
+            Some(&[("path", "ipsum/dolor")]),
+        ),
+        ("/{*:path}/edit", "/a/b/edit", Some(&[("path", "a/b")])),
+        (
+            "/{*:path}/edit",
+            "/a/edit/b/edit",
+            Some(&[("path", "a/edit/b")]),
+        ),
+        ("/{*:path}/edit", "/edit", None),
+        (
+            "/{*:a}.{b}",
+            "/x/file.tar.gz",
+            Some(&[("a", "x/file"), ("b", "tar.gz")]),
+        ),
+        // Numbered captures
+        ("/{2:path}/edit", "/a/b/edit", Some(&[("path", "a/b")])),
+        ("/{2:path}/edit", "/a/edit", None),
+        ("/{2:path}/edit", "/a/b/c/edit", None),
+        ("/{3:path}", "/a/b/c", Some(&[("path", "a/b/c")])),
+        ("/{3:path}", "/a/b", None),
+        ("/{2:path}", "/a//b", None),
+        // Optional sections
+        ("/lorem[/ipsum]", "/lorem", Some(&[])),
+        ("/lorem[/ipsum]", "/lorem/ipsum", Some(&[])),
+        ("/lorem[/ipsum]", "/lorem/dolor", None),
+        ("/lorem[/{ipsum}]", "/lorem", Some(&[])),
+        (
+            "/lorem[/{ipsum}]",
+            "/lorem/dolor",
+            Some(&[("ipsum", "dolor")]),
+        ),
+        ("/lorem[/{ipsum}]/dolor", "/lorem/dolor", Some(&[])),
+        (
+            "/lorem[/{ipsum}]/dolor",
+            "/lorem/sit/dolor",
+            Some(&[("ipsum", "sit")]),
+        ),
+        (
+            "/lorem[/ipsum]/{dolor}",
+            "/lorem/ipsum",
+            Some(&[("dolor", "ipsum")]),
+        ),
+        (
+            "/lorem[/ipsum]/{dolor}",
+            "/lorem/ipsum/sit",
+            Some(&[("dolor", "sit")]),
+        ),
+        (
+            "/{lorem}[/ipsum]",
+            "/dolor/ipsum",
+            Some(&[("lorem", "dolor")]),
+        ),
+        (
+            "/{*:lorem}[/ipsum]",
+            "/dolor/sit",
+            Some(&[("lorem", "dolor/sit")]),
+        ),
+        (
+            "/lorem[/ipsum{any}]",
+            "/lorem/ipsumdolor",
+            Some(&[("any", "dolor")]),
+        ),
+        // Queries and fragments
+        (
+            "/search?q={query}",
+            "/search?q=lorem",
+            Some(&[("query", "lorem")]),
+        ),
+        (
+            "/search?q={query}&page={page}",
+            "/search?q=lorem&page=2",
+            Some(&[("query", "lorem"), ("page", "2")]),
+        ),
+        ("/search?q={query}", "/search?q=lorem&page=2", None),
+        (
+            "/docs#{section}",
+            "/docs#intro",
+            Some(&[("section", "intro")]),
+        ),
+        (
+            "/docs?page={page}#{section}",
+            "/docs?page=2#intro",
+            Some(&[("page", "2"), ("section", "intro")]),
+        ),
+        ("/docs[#{section}]", "/docs", Some(&[])),
+        // Allowed captures
+        (
+            "/{kind(cat|dog)}/{name}",
+            "/cat/tom",
+            Some(&[("kind", "cat"), ("name", "tom")]),
+        ),
+        ("/{kind(cat|dog)}/{name}", "/cow/tom", None),
+        (
+            "/{kind(dog|cat-dog)}-{name}",
+            "/cat-dog-rex",
+            Some(&[("kind", "cat-dog"), ("name", "rex")]),
+        ),
+        // Percent-encoding
+        ("/tag/{tag}", "/tag/c%2B%2B", Some(&[("tag", "c++")])),
+        (
+            "/c++/{lorem}",
+            "/c%2B%2B/ipsum",
+            Some(&[("lorem", "ipsum")]),
+        ),
+        ("/{a}/{b}", "/x%2Fy/z", Some(&[("a", "x/y"), ("b", "z")])),
+    ];
+
+    fn match_case(
+        matcher: &str,
+        route: &str,
+        settings: MatcherSettings,
+    ) -> Option<Vec<(String, String)>> {
+        let tokens =
+            yew_router_route_parser::parse_str_and_optimize_tokens(matcher, !settings.strict)
+                .expect("should parse");
+        let result: IResult<&str, Vec<(&str, String)>> =
+            match_path_recorded(&tokens, settings, route, &mut Recorder::disabled());
+        result.ok().map(|(_, captures)| {
+            captures
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn match_cases() {
+        for (matcher, route, expected) in CASES {
+            let expected = expected.map(|captures| {
+                captures
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect::<Vec<_>>()
+            });
+            assert_eq!(
+                match_case(matcher, route, MatcherSettings::default()),
+                expected,
+                "'{}' matching '{}'",
+                matcher,
+                route
+            );
+        }
+    }
+
+    #[test]
+    fn match_cases_case_insensitive() {
+        let settings = MatcherSettings {
+            case_insensitive: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            match_case("/lorem/{ipsum}-Dolor", "/LOREM/Sit-doLor", settings),
+            Some(vec![("ipsum".to_string(), "Sit".to_string())])
+        );
+        assert_eq!(
+            match_case(
+                "/lorem/{ipsum}-Dolor",
+                "/LOREM/Sit-doLor",
+                MatcherSettings::default()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn match_cases_incomplete() {
+        let settings = MatcherSettings {
+            complete: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            match_case("/lorem/{ipsum}", "/lorem/dolor/sit", settings),
+            Some(vec![("ipsum".to_string(), "dolor".to_string())])
+        );
+        assert_eq!(
+            match_case("/{*:lorem}/ipsum", "/dolor/ipsum/ipsum", settings),
+            Some(vec![("lorem".to_string(), "dolor".to_string())])
+        );
+    }
+
+    #[test]
+    fn match_cases_strict() {
+        let settings = MatcherSettings {
+            strict: true,
+            ..Default::default()
+        };
+        assert!(match_case("/lorem", "/lorem/", settings).is_none());
+        assert!(match_case("/lorem/", "/lorem/", settings).is_some());
+    }
+
+    #[test]
+    fn backtracking_discards_abandoned_captures() {
+        assert_eq!(
+            match_case(
+                "/lorem[/{ipsum}.json]/{sit}.json",
+                "/lorem/amet.json",
+                MatcherSettings::default()
+            ),
+            Some(vec![("sit".to_string(), "amet".to_string())])
+        );
+    }
+}
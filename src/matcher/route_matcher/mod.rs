@@ -1,24 +1,39 @@
 //! Module for matching route strings based on tokens generated from the yew_router_route_parser crate.
 
 pub use yew_router_route_parser::{
-    Capture, CaptureVariant, MatcherToken,
+    Capture, CaptureVariant, MatcherToken, Specificity,
 };
 
 mod match_paths;
+mod report;
 mod util;
 
+pub use self::report::{MatchReport, MatchStep, StepOutcome};
+
 use super::Captures;
 //use super::Matcher;
+use crate::matcher::encoding;
 use crate::matcher::YewRouterParseError;
-use nom::combinator::all_consuming;
 use nom::IResult;
+#[cfg(feature = "serde")]
+use serde::de::Error;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashSet;
-use yew_router_route_parser::{optimize_tokens, parser};
+use std::fmt::{self, Display, Formatter};
+use std::ops::Range;
+use yew_router_route_parser::{optimize_tokens, parser, print_matcher_tokens};
+#[cfg(feature = "serde")]
+use yew_router_route_parser::validate_matcher_tokens;
 
 /// Attempts to match routes, transform the route to Component props and render that Component.
 ///
 /// The CTX refers to the context of the parent rendering this (The Router).
+///
+/// With the "serde" feature, it can be serialized and deserialized.
+/// When deserialized, the tokens are checked to uphold the same invariants that the parser enforces.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RouteMatcher {
     /// Tokens used to determine how the matcher will match a route string.
     pub tokens: Vec<MatcherToken>,
@@ -27,7 +42,10 @@ pub struct RouteMatcher {
 }
 
 /// Settings used for the matcher (and optimization of the parsed tokens that make up the matcher).
+///
+/// Fields that are missing when deserializing take their default values.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
 pub struct MatcherSettings {
     /// Disallow insertion of Optional `/` at the end of paths.
     pub strict: bool,
@@ -35,6 +53,11 @@ pub struct MatcherSettings {
     pub complete: bool,
     /// All literal matches do not care about case.
     pub case_insensitive: bool,
+    /// Percent-decode captured values.
+    ///
+    /// Captures that span multiple sections (`{*:name}`) are decoded section by section,
+    /// so encoded `/`s (`%2F`) within their sections are left encoded.
+    pub decode_captures: bool,
 }
 
 impl Default for MatcherSettings {
@@ -43,10 +66,29 @@ impl Default for MatcherSettings {
             strict: false,
             complete: true,
             case_insensitive: false,
+            decode_captures: true,
         }
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for RouteMatcher {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct RouteMatcherFields {
+            tokens: Vec<MatcherToken>,
+            #[serde(default)]
+            settings: MatcherSettings,
+        }
+        let fields = RouteMatcherFields::deserialize(deserializer)?;
+        validate_matcher_tokens(&fields.tokens).map_err(D::Error::custom)?;
+        Ok(RouteMatcher {
+            tokens: fields.tokens,
+            settings: fields.settings,
+        })
+    }
+}
+
 impl RouteMatcher {
     /// Attempt to create a RouteMatcher from a "matcher string".
     pub fn try_from(i: &str) -> Result<Self, YewRouterParseError> {
@@ -68,11 +110,7 @@ impl RouteMatcher {
         &'b self,
         i: &'a str,
     ) -> IResult<&'a str, Captures<'a>> {
-        if self.settings.complete {
-            all_consuming(match_paths::match_path(&self.tokens, &self.settings))(i)
-        } else {
-            match_paths::match_path(&self.tokens, &self.settings)(i)
-        }
+        match_paths::match_path(&self.tokens, &self.settings)(i)
     }
 
     /// Match a route string, collecting the results into a vector.
@@ -80,11 +118,52 @@ impl RouteMatcher {
         &'b self,
         i: &'a str,
     ) -> IResult<&'a str, Vec<(&'b str, String)>> {
-        if self.settings.complete {
-            all_consuming(match_paths::match_path_list(&self.tokens, &self.settings))(i)
-        } else {
-            match_paths::match_path_list(&self.tokens, &self.settings)(i)
-        }
+        match_paths::match_path_list(&self.tokens, &self.settings)(i)
+    }
+
+    /// Match a route string, collecting the names of the captures and the byte ranges of the
+    /// route that they span.
+    ///
+    /// Unlike the other `capture_route_*` methods, this doesn't copy the captured sections out
+    /// of the route, so they aren't percent-decoded.
+    ///
+    /// # Example
+    /// ```
+    ///# use yew_router::matcher::RouteMatcher;
+    /// let matcher = RouteMatcher::try_from("/lorem/{ipsum}").unwrap();
+    /// let route = "/lorem/dolor";
+    /// let (_, spans) = matcher.capture_route_spans(route).unwrap();
+    /// assert_eq!(spans, vec![("ipsum", 7..12)]);
+    /// assert_eq!(&route[spans[0].1.clone()], "dolor");
+    /// ```
+    pub fn capture_route_spans<'a, 'b: 'a>(
+        &'b self,
+        i: &'a str,
+    ) -> IResult<&'a str, Vec<(&'b str, Range<usize>)>> {
+        match_paths::match_path_spans(&self.tokens, &self.settings)(i)
+    }
+
+    /// Explains how the matcher attempts to match the route string.
+    ///
+    /// The report records which token consumed which section of the route,
+    /// where matching stopped, and which delimiters were used to terminate captures.
+    ///
+    /// # Example
+    /// ```
+    ///# use yew_router::matcher::RouteMatcher;
+    /// let matcher = RouteMatcher::try_from("/lorem/{ipsum}").unwrap();
+    /// let report = matcher.explain("/dolor/sit");
+    /// assert!(!report.matched);
+    /// assert_eq!(report.stopped_at, 0);
+    /// ```
+    pub fn explain(&self, route: &str) -> MatchReport {
+        let mut recorder = report::Recorder::enabled();
+        let result: IResult<&str, Captures> =
+            match_paths::match_path_recorded(&self.tokens, self.settings, route, &mut recorder);
+        let end = result
+            .ok()
+            .map(|(remaining, _)| route.len() - remaining.len());
+        recorder.into_report(route, end)
     }
 
     /// Gets a set of all names that will be captured.
@@ -116,6 +195,131 @@ impl RouteMatcher {
         }
         capture_names_impl(&self.tokens)
     }
+
+    /// Gets how specific the matcher is.
+    ///
+    /// When more than one matcher matches a route, the most specific one is usually the one
+    /// that was meant to match it.
+    ///
+    /// # Example
+    /// ```
+    ///# use yew_router::matcher::RouteMatcher;
+    /// let new_user = RouteMatcher::try_from("/users/new").unwrap();
+    /// let user = RouteMatcher::try_from("/users/{id}").unwrap();
+    /// assert!(new_user.specificity() > user.specificity());
+    /// ```
+    pub fn specificity(&self) -> Specificity {
+        Specificity::of(&self.tokens)
+    }
+
+    /// Builds a route string that this matcher would match, using the provided values for its captures.
+    ///
+    /// Values are percent-encoded before being inserted into the route,
+    /// and so are escaped and non-ASCII characters in the exact sections of the matcher.
+    /// Optional sections are included only if they contain captures, all of which have values.
+    ///
+    /// Returns `None` if a required capture is missing a value, if a value isn't one of a capture's
+    /// allowed values, or if the matcher contains unnamed captures.
+    ///
+    /// # Example
+    /// ```
+    ///# use yew_router::matcher::{Captures, RouteMatcher};
+    /// let matcher = RouteMatcher::try_from("/tag/{name}[/{page}]").unwrap();
+    /// let mut values = Captures::new();
+    /// values.insert("name", "c++".to_string());
+    /// assert_eq!(matcher.build_route(&values), Some("/tag/c%2B%2B".to_string()));
+    ///
+    /// values.insert("page", "2".to_string());
+    /// assert_eq!(matcher.build_route(&values), Some("/tag/c%2B%2B/2".to_string()));
+    /// ```
+    pub fn build_route(&self, values: &Captures) -> Option<String> {
+        fn build_route_impl(
+            tokens: &[MatcherToken],
+            values: &Captures,
+            route: &mut String,
+        ) -> Option<()> {
+            for token in tokens {
+                match token {
+                    MatcherToken::Exact(literal) => {
+                        route.push_str(&encoding::encode_exact(literal))
+                    }
+                    MatcherToken::Optional(inner) => {
+                        let mut optional_route = String::new();
+                        if contains_captures(inner)
+                            && build_route_impl(inner, values, &mut optional_route)
+                                .is_some()
+                        {
+                            route.push_str(&optional_route);
+                        }
+                    }
+                    MatcherToken::Capture(capture) => {
+                        let (value, encode): (_, fn(&str) -> String) =
+                            match &capture.capture_variant {
+                                CaptureVariant::Named(name) => (
+                                    values.get(name.as_str())?,
+                                    encoding::encode_section,
+                                ),
+                                CaptureVariant::ManyNamed(name)
+                                | CaptureVariant::NumberedNamed { name, .. } => (
+                                    values.get(name.as_str())?,
+                                    encoding::encode_sections,
+                                ),
+                                CaptureVariant::Unnamed
+                                | CaptureVariant::ManyUnnamed
+                                | CaptureVariant::NumberedUnnamed { .. } => return None,
+                            };
+                        if let Some(allowed_captures) = &capture.allowed_captures {
+                            if !allowed_captures.contains(value) {
+                                return None;
+                            }
+                        }
+                        route.push_str(&encode(value));
+                    }
+                }
+            }
+            Some(())
+        }
+        fn contains_captures(tokens: &[MatcherToken]) -> bool {
+            tokens.iter().any(|token| match token {
+                MatcherToken::Exact(_) => false,
+                MatcherToken::Capture(_) => true,
+                MatcherToken::Optional(inner) => contains_captures(inner),
+            })
+        }
+
+        let mut route = String::new();
+        build_route_impl(&self.tokens, values, &mut route)?;
+        Some(route)
+    }
+
+    /// Prints the matcher as a normalized "matcher string".
+    ///
+    /// The optional `/`s inserted for non-strict matchers are omitted,
+    /// so the string can be used to recreate this matcher with the same settings.
+    /// The settings themselves are not part of the string.
+    ///
+    /// # Example
+    /// ```
+    ///# use yew_router::matcher::RouteMatcher;
+    /// let matcher = RouteMatcher::try_from("/lorem/{ipsum}?dolor={sit}").unwrap();
+    /// assert_eq!(matcher.to_matcher_string(), "/lorem/{ipsum}?dolor={sit}");
+    /// ```
+    pub fn to_matcher_string(&self) -> String {
+        let optional_slash = MatcherToken::Optional(vec![MatcherToken::Exact("/".to_string())]);
+        let tokens: Vec<MatcherToken> = self
+            .tokens
+            .iter()
+            .filter(|token| self.settings.strict || **token != optional_slash)
+            .cloned()
+            .collect();
+        print_matcher_tokens(&tokens)
+    }
+}
+
+impl Display for RouteMatcher {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.to_matcher_string())
+    }
 }
 
 #[cfg(test)]
@@ -142,6 +346,189 @@ mod tests {
         }
     }
 
+    #[test]
+    fn capture_spans() {
+        let matcher = RouteMatcher::try_from("/lorem/{ipsum}/{*:dolor}?sit={amet}")
+            .expect("should parse");
+        let route = "/lorem/a%20b/c/d?sit=e";
+        let (_, spans) = matcher
+            .capture_route_spans(route)
+            .expect("should match");
+        assert_eq!(
+            spans,
+            vec![("ipsum", 7..12), ("dolor", 13..16), ("amet", 21..22)]
+        );
+        assert_eq!(&route[spans[0].1.clone()], "a%20b");
+    }
+
+    #[test]
+    fn capture_spans_agree_with_captured_strings() {
+        let matcher = RouteMatcher::try_from("/{lorem}-{ipsum}/{2:dolor}")
+            .expect("should parse");
+        let route = "/a-b-c/d/e";
+        let (_, spans) = matcher
+            .capture_route_spans(route)
+            .expect("should match");
+        let (_, strings) = matcher
+            .capture_route_into_vec(route)
+            .expect("should match");
+        let from_spans: Vec<(&str, String)> = spans
+            .into_iter()
+            .map(|(name, span)| (name, route[span].to_string()))
+            .collect();
+        assert_eq!(from_spans, strings);
+    }
+
+    #[test]
+    fn explain_matched_route() {
+        let matcher = RouteMatcher::try_from("/lorem/{ipsum}").expect("should parse");
+        let report = matcher.explain("/lorem/dolor");
+        let expected = MatchReport {
+            route: "/lorem/dolor".to_string(),
+            steps: vec![
+                MatchStep {
+                    token: "/lorem/".to_string(),
+                    depth: 0,
+                    span: 0..7,
+                    outcome: StepOutcome::Matched,
+                    delimiters: vec![],
+                },
+                MatchStep {
+                    token: "{ipsum}".to_string(),
+                    depth: 0,
+                    span: 7..12,
+                    outcome: StepOutcome::Matched,
+                    delimiters: vec![],
+                },
+            ],
+            matched: true,
+            stopped_at: 12,
+        };
+        assert_eq!(report, expected);
+    }
+
+    #[test]
+    fn explain_failed_route() {
+        let matcher = RouteMatcher::try_from("/lorem/{ipsum}").expect("should parse");
+        let report = matcher.explain("/dolor/sit");
+        assert!(!report.matched);
+        assert_eq!(report.stopped_at, 0);
+        assert_eq!(report.steps.len(), 1);
+        assert_eq!(report.steps[0].outcome, StepOutcome::Failed);
+        assert_eq!(report.steps[0].span, 0..0);
+    }
+
+    #[test]
+    fn explain_unconsumed_route() {
+        let matcher = RouteMatcher::try_from("/lorem").expect("should parse");
+        let report = matcher.explain("/lorem/ipsum");
+        assert!(!report.matched);
+        assert_eq!(report.stopped_at, 7);
+        assert!(report
+            .steps
+            .iter()
+            .all(|step| step.outcome != StepOutcome::Failed));
+        assert!(report.to_string().contains("'ipsum' was not consumed"));
+    }
+
+    #[test]
+    fn explain_capture_delimiters() {
+        let matcher = RouteMatcher::try_from("/{lorem}/ipsum").expect("should parse");
+        let report = matcher.explain("/dolor/ipsum");
+        assert!(report.matched);
+        assert_eq!(report.steps[1].token, "{lorem}");
+        assert_eq!(report.steps[1].span, 1..6);
+        assert_eq!(report.steps[1].delimiters, vec!["/ipsum".to_string()]);
+    }
+
+    #[test]
+    fn explain_skipped_optional() {
+        let matcher = RouteMatcher::try_from("/lorem[/{ipsum}]").expect("should parse");
+        let report = matcher.explain("/lorem");
+        assert!(report.matched);
+        let optional = &report.steps[1];
+        assert_eq!(optional.token, "[/{ipsum}]");
+        assert_eq!(optional.outcome, StepOutcome::Skipped);
+        let inner = &report.steps[2];
+        assert_eq!(inner.token, "/");
+        assert_eq!(inner.depth, 1);
+        assert_eq!(inner.outcome, StepOutcome::Failed);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        let settings = MatcherSettings {
+            case_insensitive: true,
+            ..Default::default()
+        };
+        let matcher = RouteMatcher::new("/lorem/{ipsum}[/{*:dolor}]", settings)
+            .expect("should parse");
+        let serialized = serde_json::to_string(&matcher).expect("should serialize");
+        let deserialized: RouteMatcher =
+            serde_json::from_str(&serialized).expect("should deserialize");
+        assert_eq!(deserialized, matcher);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_uses_default_settings() {
+        let json = r#"{"tokens": [{"Exact": "/lorem"}], "settings": {"strict": true}}"#;
+        let matcher: RouteMatcher = serde_json::from_str(json).expect("should deserialize");
+        let expected = MatcherSettings {
+            strict: true,
+            ..Default::default()
+        };
+        assert_eq!(matcher.settings, expected);
+
+        let json = r#"{"tokens": [{"Exact": "/lorem"}]}"#;
+        let matcher: RouteMatcher = serde_json::from_str(json).expect("should deserialize");
+        assert_eq!(matcher.settings, MatcherSettings::default());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_rejects_adjacent_captures() {
+        let json = r#"{"tokens": [
+            {"Exact": "/"},
+            {"Capture": {"capture_variant": {"Named": "lorem"}, "allowed_captures": null}},
+            {"Optional": [{"Exact": "-"}]},
+            {"Capture": {"capture_variant": "Unnamed", "allowed_captures": null}}
+        ]}"#;
+        let error =
+            serde_json::from_str::<RouteMatcher>(json).expect_err("should not deserialize");
+        assert!(error.to_string().starts_with("Captures can't be next to each other."));
+    }
+
+    #[test]
+    fn to_matcher_string_omits_inserted_optional_slashes() {
+        let matcher = RouteMatcher::try_from("/lorem[/{ipsum}]").expect("should parse");
+        assert_eq!(matcher.to_string(), "/lorem[/{ipsum}]");
+    }
+
+    #[test]
+    fn to_matcher_string_round_trips() {
+        let strict = MatcherSettings {
+            strict: true,
+            ..Default::default()
+        };
+        let matcher_strings = [
+            "/",
+            "/lorem/ipsum/",
+            "/lorem/{*:ipsum}",
+            r"/files/report\(1\).pdf",
+            "/lorem?ipsum={dolor}#{sit}",
+        ];
+        for settings in &[MatcherSettings::default(), strict] {
+            for i in &matcher_strings {
+                let matcher = RouteMatcher::new(i, *settings).expect("should parse");
+                let printed = matcher.to_matcher_string();
+                assert_eq!(&printed, i);
+                assert_eq!(RouteMatcher::new(&printed, *settings), Ok(matcher));
+            }
+        }
+    }
+
     #[test]
     fn basic_separator() {
         let tokens = vec![RouteParserToken::Separator];
@@ -272,6 +659,108 @@ mod tests {
             .expect("should parse");
     }
 
+    #[test]
+    fn captures_are_percent_decoded() {
+        let matcher = RouteMatcher::try_from("/tag/{name}").expect("should parse");
+        let (_, matches) = matcher
+            .capture_route_into_map("/tag/c%2B%2B")
+            .expect("should parse");
+        assert_eq!(matches["name"], "c++".to_string())
+    }
+
+    #[test]
+    fn captures_are_not_decoded_if_disabled() {
+        let settings = MatcherSettings {
+            decode_captures: false,
+            ..MatcherSettings::default()
+        };
+        let matcher =
+            RouteMatcher::new("/tag/{name}", settings).expect("should parse");
+        let (_, matches) = matcher
+            .capture_route_into_map("/tag/lorem%20ipsum")
+            .expect("should parse");
+        assert_eq!(matches["name"], "lorem%20ipsum".to_string())
+    }
+
+    #[test]
+    fn many_captures_are_decoded_by_section() {
+        let matcher = RouteMatcher::try_from("/files/{*:path}").expect("should parse");
+        let (_, matches) = matcher
+            .capture_route_into_map("/files/my%20files/a%2Fb")
+            .expect("should parse");
+        assert_eq!(matches["path"], "my files/a%2Fb".to_string())
+    }
+
+    #[test]
+    fn many_captures_are_not_decoded_if_disabled() {
+        let settings = MatcherSettings {
+            decode_captures: false,
+            ..MatcherSettings::default()
+        };
+        let matcher = RouteMatcher::new("/files/{*:path}", settings).expect("should parse");
+        let (_, matches) = matcher
+            .capture_route_into_map("/files/my%20files/report")
+            .expect("should parse");
+        assert_eq!(matches["path"], "my%20files/report".to_string())
+    }
+
+    #[test]
+    fn exact_matches_percent_encoded_route() {
+        let matcher = RouteMatcher::try_from("/café/{name}").expect("should parse");
+        let (_, matches) = matcher
+            .capture_route_into_map("/caf%C3%A9/lorem")
+            .expect("should parse");
+        assert_eq!(matches["name"], "lorem".to_string())
+    }
+
+    #[test]
+    fn build_route_encodes_values() {
+        let matcher = RouteMatcher::try_from("/tag/{name}").expect("should parse");
+        let mut values = Captures::new();
+        values.insert("name", "lorem ipsum/dolor".to_string());
+        let route = matcher.build_route(&values).expect("should build");
+        assert_eq!(route, "/tag/lorem%20ipsum%2Fdolor".to_string());
+        let (_, matches) = matcher
+            .capture_route_into_map(&route)
+            .expect("should parse");
+        assert_eq!(matches["name"], "lorem ipsum/dolor".to_string())
+    }
+
+    #[test]
+    fn build_route_keeps_slashes_in_many_captures() {
+        let matcher = RouteMatcher::try_from("/files/{*:path}").expect("should parse");
+        let mut values = Captures::new();
+        values.insert("path", "my files/report".to_string());
+        assert_eq!(
+            matcher.build_route(&values),
+            Some("/files/my%20files/report".to_string())
+        );
+    }
+
+    #[test]
+    fn build_route_encodes_exact_sections() {
+        let matcher =
+            RouteMatcher::try_from("/café/report\\(1\\)?lorem={ipsum}").expect("should parse");
+        let mut values = Captures::new();
+        values.insert("ipsum", "dolor".to_string());
+        assert_eq!(
+            matcher.build_route(&values),
+            Some("/caf%C3%A9/report%281%29?lorem=dolor".to_string())
+        );
+    }
+
+    #[test]
+    fn build_route_missing_value() {
+        let matcher = RouteMatcher::try_from("/tag/{name}").expect("should parse");
+        assert_eq!(matcher.build_route(&Captures::new()), None);
+    }
+
+    #[test]
+    fn build_route_rejects_unnamed_captures() {
+        let matcher = RouteMatcher::try_from("/tag/{}").expect("should parse");
+        assert_eq!(matcher.build_route(&Captures::new()), None);
+    }
+
     #[test]
     fn match_many_named() {
         let tokens = vec![
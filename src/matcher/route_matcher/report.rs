@@ -0,0 +1,195 @@
+//! Reports explaining how a route was matched.
+use std::fmt::{self, Display, Formatter};
+use std::ops::Range;
+use yew_router_route_parser::MatcherToken;
+
+/// An explanation of how a `RouteMatcher` attempted to match a route.
+///
+/// It can be acquired by calling `RouteMatcher::explain`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchReport {
+    /// The route that was matched against.
+    pub route: String,
+    /// The steps that were taken, in the order that they were attempted.
+    ///
+    /// The steps for the tokens within an optional section directly follow the step for the
+    /// optional section itself.
+    /// Because the matcher backtracks, a token may be attempted more than once,
+    /// for example when a capture is retried with a different length.
+    pub steps: Vec<MatchStep>,
+    /// Whether the route matched.
+    pub matched: bool,
+    /// The byte offset into the route where matching stopped.
+    ///
+    /// If the route didn't match, this is the furthest offset that any token reached.
+    /// If the route matched, this is where the consumed portion of the route ends.
+    pub stopped_at: usize,
+}
+
+/// A single token's attempt at matching a section of the route.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchStep {
+    /// The token, printed as a "matcher string".
+    pub token: String,
+    /// How many optional sections the token is nested in.
+    pub depth: usize,
+    /// The bytes of the route that were consumed by the token.
+    ///
+    /// If the token didn't match, the span is empty and starts where the token began matching.
+    pub span: Range<usize>,
+    /// Whether the token matched.
+    pub outcome: StepOutcome,
+    /// For captures, the strings that could terminate the capture, in the order they were tried.
+    pub delimiters: Vec<String>,
+}
+
+/// The outcome of a `MatchStep`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StepOutcome {
+    /// The token matched.
+    Matched,
+    /// The token didn't match, causing its enclosing section to fail.
+    Failed,
+    /// The optional section didn't match, so it was skipped.
+    Skipped,
+}
+
+impl Display for MatchReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.matched {
+            writeln!(f, "'{}' matched:", self.route)?;
+        } else {
+            writeln!(
+                f,
+                "'{}' did not match, stopping at {}:",
+                self.route, self.stopped_at
+            )?;
+        }
+        for step in &self.steps {
+            write!(f, "{}", "  ".repeat(step.depth + 1))?;
+            let outcome = match step.outcome {
+                StepOutcome::Matched => "matched",
+                StepOutcome::Failed => "failed",
+                StepOutcome::Skipped => "skipped",
+            };
+            write!(
+                f,
+                "'{}' {} '{}' ({}..{})",
+                step.token,
+                outcome,
+                &self.route[step.span.clone()],
+                step.span.start,
+                step.span.end
+            )?;
+            if !step.delimiters.is_empty() {
+                write!(f, ", delimited by {:?}", step.delimiters)?;
+            }
+            writeln!(f)?;
+        }
+        if !self.matched {
+            writeln!(
+                f,
+                "  '{}' was not consumed",
+                &self.route[self.stopped_at..]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects steps while a route is being matched, if a report was requested.
+#[derive(Debug)]
+pub(super) struct Recorder {
+    steps: Option<Vec<MatchStep>>,
+}
+
+impl Recorder {
+    /// A recorder that doesn't record anything.
+    pub fn disabled() -> Self {
+        Recorder { steps: None }
+    }
+
+    /// A recorder that records the steps taken to match the route.
+    pub fn enabled() -> Self {
+        Recorder {
+            steps: Some(vec![]),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.steps.is_some()
+    }
+
+    /// Records that the token began matching at the given offset.
+    ///
+    /// Returns the index of the step, which is used to finish it.
+    pub fn begin(
+        &mut self,
+        token: &MatcherToken,
+        depth: usize,
+        start: usize,
+        delimiters: Vec<String>,
+    ) -> Option<usize> {
+        self.steps.as_mut().map(|steps| {
+            steps.push(MatchStep {
+                token: token.to_string(),
+                depth,
+                span: start..start,
+                outcome: StepOutcome::Failed,
+                delimiters,
+            });
+            steps.len() - 1
+        })
+    }
+
+    /// Records the outcome of a step, given the offset where the token stopped matching.
+    pub fn finish(&mut self, step: Option<usize>, outcome: StepOutcome, end: usize) {
+        if let (Some(steps), Some(index)) = (self.steps.as_mut(), step) {
+            let step = &mut steps[index];
+            if outcome == StepOutcome::Matched {
+                step.span.end = end;
+            }
+            step.outcome = outcome;
+        }
+    }
+
+    /// Records that an optional section was skipped.
+    ///
+    /// If the section had matched before the matcher backtracked,
+    /// the skip is recorded as a new step.
+    pub fn skip(&mut self, step: Option<usize>, token: &MatcherToken, depth: usize, start: usize) {
+        let is_pending = match (self.steps.as_ref(), step) {
+            (Some(steps), Some(index)) => steps[index].outcome == StepOutcome::Failed,
+            _ => return,
+        };
+        let step = if is_pending {
+            step
+        } else {
+            self.begin(token, depth, start, vec![])
+        };
+        self.finish(step, StepOutcome::Skipped, start);
+    }
+
+    /// Finishes recording, producing a report.
+    ///
+    /// `end` is where the match ended, or `None` if matching failed.
+    pub fn into_report(self, route: &str, end: Option<usize>) -> MatchReport {
+        let steps = self.steps.unwrap_or_default();
+        let stopped_at = end.unwrap_or_else(|| {
+            steps
+                .iter()
+                .map(|step| match step.outcome {
+                    StepOutcome::Matched => step.span.end,
+                    StepOutcome::Failed | StepOutcome::Skipped => step.span.start,
+                })
+                .max()
+                .unwrap_or(0)
+        });
+        MatchReport {
+            route: route.to_string(),
+            steps,
+            matched: end.is_some(),
+            stopped_at,
+        }
+    }
+}
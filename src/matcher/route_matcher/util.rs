@@ -1,20 +1,42 @@
-use nom::bytes::complete::{tag, tag_no_case};
-use nom::combinator::{cond, map};
-use nom::sequence::pair;
+use crate::matcher::encoding::{decode_escape, is_delimiter};
+use nom::error::ErrorKind;
 use nom::IResult;
 
 /// Allows a configurable tag that can optionally be case insensitive.
+///
+/// The input is allowed to contain percent-encoded forms of the characters in the tag,
+/// so `/c%2B%2B` will be matched by the tag `/c++`.
+/// Percent-encoded delimiters (`/`, `?`, `#`, `&`, `=`) are not considered equivalent to
+/// their unencoded counterparts.
 pub fn tag_possibly_case_sensitive<'a, 'b: 'a>(
     text: &'b str,
     is_sensitive: bool,
 ) -> impl Fn(&'a str) -> IResult<&'a str, &'a str> {
-    map(
-        pair(
-            cond(is_sensitive, tag(text)),
-            cond(!is_sensitive, tag_no_case(text)),
-        ),
-        |(x, y): (Option<&str>, Option<&str>)| x.xor(y).unwrap(),
-    )
+    move |i: &'a str| {
+        let input = i.as_bytes();
+        let mut index = 0;
+        for &expected in text.as_bytes() {
+            let (byte, width) = match input.get(index) {
+                Some(&byte) => match decode_escape(&input[index..]) {
+                    Some(decoded) if !is_delimiter(decoded) => (decoded, 3),
+                    _ => (byte, 1),
+                },
+                None => return Err(nom::Err::Error((i, ErrorKind::Tag))),
+            };
+            let equal = if is_sensitive {
+                byte == expected
+            } else {
+                byte.eq_ignore_ascii_case(&expected)
+            };
+            if !equal {
+                return Err(nom::Err::Error((i, ErrorKind::Tag)));
+            }
+            index += width;
+        }
+        // The matched portion of the input can't split a multi-byte character,
+        // because every byte in it was equal to a byte in the valid utf-8 tag.
+        Ok((&i[index..], &i[..index]))
+    }
 }
 
 #[cfg(test)]
@@ -33,4 +55,24 @@ mod test {
         parser("lorem").expect("Should match");
         parser("LoREm").expect("Should match");
     }
+
+    #[test]
+    fn percent_encoded_input() {
+        let parser = tag_possibly_case_sensitive("/c++", true);
+        assert_eq!(parser("/c%2B%2B/rest"), Ok(("/rest", "/c%2B%2B")));
+        assert_eq!(parser("/c%2b+"), Ok(("", "/c%2b+")));
+    }
+
+    #[test]
+    fn percent_encoded_multibyte_input() {
+        let parser = tag_possibly_case_sensitive("/café", true);
+        parser("/caf%C3%A9").expect("Should match");
+        parser("/café").expect("Should match");
+    }
+
+    #[test]
+    fn percent_encoded_delimiter_is_not_equivalent() {
+        let parser = tag_possibly_case_sensitive("/lorem/ipsum", true);
+        parser("/lorem%2Fipsum").expect_err("Should not match");
+    }
 }
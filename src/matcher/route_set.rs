@@ -0,0 +1,143 @@
+//! Sets of matchers that choose which of them matches a route.
+use crate::matcher::{Captures, RouteMatcher};
+
+/// How a `RouteSet` chooses between matchers that all match the same route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// The matcher that was added first is chosen.
+    FirstDeclared,
+    /// The matcher with the greatest `Specificity` is chosen.
+    ///
+    /// If more than one matcher is the most specific, the one that was added first is chosen.
+    MostSpecific,
+}
+
+impl Default for SelectionMode {
+    fn default() -> Self {
+        SelectionMode::FirstDeclared
+    }
+}
+
+/// A set of `RouteMatcher`s, each associated with a value,
+/// that finds the value whose matcher matches a route.
+///
+/// # Example
+/// ```
+///# use yew_router::matcher::{RouteMatcher, RouteSet, SelectionMode};
+/// let mut routes = RouteSet::new(SelectionMode::MostSpecific);
+/// routes.insert(RouteMatcher::try_from("/users/{id}").unwrap(), "user");
+/// routes.insert(RouteMatcher::try_from("/users/new").unwrap(), "new user");
+///
+/// let (value, _) = routes.match_route("/users/new").unwrap();
+/// assert_eq!(*value, "new user");
+/// let (value, captures) = routes.match_route("/users/12").unwrap();
+/// assert_eq!(*value, "user");
+/// assert_eq!(captures["id"], "12".to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteSet<T> {
+    mode: SelectionMode,
+    /// The matchers and their values, in the order that they are tried.
+    routes: Vec<(RouteMatcher, T)>,
+}
+
+impl<T> Default for RouteSet<T> {
+    fn default() -> Self {
+        RouteSet::new(SelectionMode::default())
+    }
+}
+
+impl<T> RouteSet<T> {
+    /// Creates an empty set that chooses between matchers using the mode.
+    pub fn new(mode: SelectionMode) -> Self {
+        RouteSet {
+            mode,
+            routes: vec![],
+        }
+    }
+
+    /// Gets how the set chooses between matchers.
+    pub fn mode(&self) -> SelectionMode {
+        self.mode
+    }
+
+    /// Adds a matcher and the value that should be found when it matches.
+    pub fn insert(&mut self, matcher: RouteMatcher, value: T) {
+        let index = match self.mode {
+            SelectionMode::FirstDeclared => self.routes.len(),
+            SelectionMode::MostSpecific => {
+                // Keeping the routes sorted means the first one to match is the most specific,
+                // and inserting after routes that are just as specific keeps declaration order.
+                let specificity = matcher.specificity();
+                self.routes
+                    .iter()
+                    .position(|(existing, _)| existing.specificity() < specificity)
+                    .unwrap_or(self.routes.len())
+            }
+        };
+        self.routes.insert(index, (matcher, value));
+    }
+
+    /// Iterates over the matchers and their values, in the order that they are tried.
+    pub fn iter(&self) -> impl Iterator<Item = &(RouteMatcher, T)> {
+        self.routes.iter()
+    }
+
+    /// Finds the value of the chosen matcher that matches the route, along with its captures.
+    pub fn match_route<'a>(&'a self, route: &'a str) -> Option<(&'a T, Captures<'a>)> {
+        self.routes.iter().find_map(|(matcher, value)| {
+            matcher
+                .capture_route_into_map(route)
+                .ok()
+                .map(|(_, captures)| (value, captures))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn route_set(mode: SelectionMode, matchers: &[&'static str]) -> RouteSet<&'static str> {
+        let mut routes = RouteSet::new(mode);
+        for matcher in matchers {
+            routes.insert(RouteMatcher::try_from(matcher).expect("should parse"), *matcher);
+        }
+        routes
+    }
+
+    fn matched(routes: &RouteSet<&'static str>, route: &str) -> Option<&'static str> {
+        routes.match_route(route).map(|(value, _)| *value)
+    }
+
+    #[test]
+    fn first_declared_match_is_chosen() {
+        let routes = route_set(SelectionMode::FirstDeclared, &["/users/{id}", "/users/new"]);
+        assert_eq!(matched(&routes, "/users/new"), Some("/users/{id}"));
+    }
+
+    #[test]
+    fn most_specific_match_is_chosen() {
+        let routes = route_set(
+            SelectionMode::MostSpecific,
+            &["/{*:path}", "/users/{id}", "/users/new"],
+        );
+        assert_eq!(matched(&routes, "/users/new"), Some("/users/new"));
+        assert_eq!(matched(&routes, "/users/12"), Some("/users/{id}"));
+        assert_eq!(matched(&routes, "/lorem/ipsum"), Some("/{*:path}"));
+    }
+
+    #[test]
+    fn equally_specific_matches_keep_declaration_order() {
+        let routes = route_set(SelectionMode::MostSpecific, &["/{lorem}", "/{ipsum}"]);
+        assert_eq!(matched(&routes, "/dolor"), Some("/{lorem}"));
+        let routes = route_set(SelectionMode::MostSpecific, &["/{ipsum}", "/{lorem}"]);
+        assert_eq!(matched(&routes, "/dolor"), Some("/{ipsum}"));
+    }
+
+    #[test]
+    fn no_match() {
+        let routes = route_set(SelectionMode::MostSpecific, &["/users/{id}"]);
+        assert_eq!(matched(&routes, "/posts/12"), None);
+    }
+}
@@ -1,24 +1,122 @@
 //! Wrapper around route url string, and associated history state.
+#[cfg(feature = "service")]
 use crate::service::RouteService;
 use serde::Deserialize;
 use serde::Serialize;
+#[cfg(feature = "service")]
 use stdweb::unstable::TryFrom;
+#[cfg(feature = "service")]
 use stdweb::JsSerialize;
+#[cfg(feature = "service")]
 use stdweb::Value;
 
 //use std::ops::Deref;
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
 use std::ops::Deref;
 
 /// Any state that can be stored by the History API must meet the criteria of this trait.
+#[cfg(feature = "service")]
 pub trait RouteState:
     Clone + Default + JsSerialize + TryFrom<Value> + 'static
 {
 }
+#[cfg(feature = "service")]
 impl<T> RouteState for T where
     T: Clone + Default + JsSerialize + TryFrom<Value> + 'static
 {
 }
 
+/// Any state that can be stored in a route must meet the criteria of this trait.
+///
+/// Without the "service" feature, routes aren't stored by the History API,
+/// so their states don't have to be convertible to and from JavaScript values.
+#[cfg(not(feature = "service"))]
+pub trait RouteState: Clone + Default + 'static {}
+#[cfg(not(feature = "service"))]
+impl<T> RouteState for T where T: Clone + Default + 'static {}
+
+/// Any state that can be managed by the `Router` must meet the criteria of this trait.
+pub trait RouterState<'de>: RouteState + Serialize + Deserialize<'de> + Debug + PartialEq {}
+
+impl<'de, T> RouterState<'de> for T where
+    T: RouteState + Serialize + Deserialize<'de> + Debug + PartialEq
+{
+}
+
+/// Route state that is stored with a version, so states stored by older versions of an application
+/// can be upgraded instead of being lost.
+///
+/// Versioning is enabled by `RouteService::set_state_versioning`,
+/// or by `RouterConfig::state_versioning` for the `RouteAgent`.
+///
+/// # Example
+/// ```
+///# use yew_router::route::{StateError, VersionedState};
+///# use serde_derive::{Deserialize, Serialize};
+///# use stdweb::unstable::TryFrom;
+///# use stdweb::{js_deserializable, js_serializable, Value};
+/// // Version 1 stored the page number, version 2 stores the page and its size.
+/// #[derive(Clone, Default, Serialize, Deserialize)]
+/// struct PageState {
+///     page: u32,
+///     size: u32,
+/// }
+/// js_serializable!(PageState);
+/// js_deserializable!(PageState);
+///
+/// impl VersionedState for PageState {
+///     const VERSION: u32 = 2;
+///
+///     fn migrate(version: u32, state: Value) -> Result<Self, StateError> {
+///         match version {
+///             1 => u32::try_from(state)
+///                 .map(|page| PageState { page, size: 20 })
+///                 .map_err(|_| StateError::Invalid { version }),
+///             _ => Err(StateError::UnknownVersion(version)),
+///         }
+///     }
+/// }
+/// ```
+#[cfg(feature = "service")]
+pub trait VersionedState: RouteState {
+    /// The version that states are stored with.
+    ///
+    /// States stored without a version, before versioning was enabled, have version 0.
+    const VERSION: u32;
+
+    /// Upgrades a state that was stored with an older version.
+    fn migrate(version: u32, state: Value) -> Result<Self, StateError>;
+}
+
+/// Reasons why the state of a history entry couldn't be converted to the route state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateError {
+    /// The state was stored with a version that can't be migrated,
+    /// like one that is newer than the current version.
+    UnknownVersion(u32),
+    /// The state couldn't be converted, or migrated, from the version it was stored with.
+    Invalid {
+        /// The version that the state was stored with.
+        version: u32,
+    },
+}
+
+impl Display for StateError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            StateError::UnknownVersion(version) => {
+                write!(f, "State version {} can't be migrated.", version)
+            }
+            StateError::Invalid { version } => {
+                write!(f, "The state stored with version {} is invalid.", version)
+            }
+        }
+    }
+}
+
+impl Error for StateError {}
+
 /// The representation of a route, segmented into different sections for easy access.
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Route<T> {
@@ -28,6 +126,32 @@ pub struct Route<T> {
     pub state: Option<T>,
 }
 
+/// What caused the route to change.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteChangeCause {
+    /// A new history entry was created by a `ChangeRoute` request.
+    Push,
+    /// The most recent history entry was replaced by a `ReplaceRoute` request.
+    Replace,
+    /// The browser navigated back or forward.
+    Pop,
+    /// The current route was requested by a `GetCurrentRoute` request.
+    Initial,
+}
+
+/// A change of route, which the `RouteAgent` sends to its subscribers.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RouteChange<T> {
+    /// The route before the change.
+    ///
+    /// This is `None` if the cause is `Initial`.
+    pub previous: Option<Route<T>>,
+    /// The route after the change.
+    pub current: Route<T>,
+    /// What caused the route to change.
+    pub cause: RouteChangeCause,
+}
+
 /// Formats a path, query, and fragment into a string.
 ///
 /// # Note
@@ -52,6 +176,7 @@ impl<T> Route<T> {
     /// It does not get the current state.
     /// That is only provided via events.
     /// See [RouteService.register_callback](struct.RouteService.html#method.register_callback) to acquire state.
+    #[cfg(feature = "service")]
     pub fn current_route(route_service: &RouteService<T>) -> Self {
         let route = route_service.get_route();
         // TODO, should try to get the state using the history api once that is exposed through stdweb.
@@ -0,0 +1,368 @@
+//! Tables of every route that a `Switch` can be produced from.
+use crate::matcher::{CaptureVariant, Captures, MatcherToken, RouteMatcher};
+use crate::switch::{Switch, SwitchDescription};
+use std::collections::HashSet;
+
+/// A route that a `Switch` can be produced from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteTableEntry {
+    /// The names of the variants that are produced, from the outermost `Switch` to the innermost.
+    pub variants: Vec<&'static str>,
+    /// The matcher for the whole route.
+    ///
+    /// The matchers of nested `Switch`es take the place of the captures they are matched against.
+    pub matcher: RouteMatcher,
+    /// The names of the captures of the matcher, from the outermost `Switch` to the innermost.
+    pub capture_names: Vec<String>,
+}
+
+impl RouteTableEntry {
+    /// Gets the route that the matcher matches when its optional sections are left out,
+    /// if it doesn't need any captures.
+    ///
+    /// It is percent-encoded like the routes built by `RouteMatcher::build_route`.
+    pub fn path(&self) -> Option<String> {
+        self.matcher.build_route(&Captures::new())
+    }
+}
+
+/// A table of every route that a `Switch` can be produced from.
+///
+/// Nested `Switch`es are flattened into the table, so there is an entry for every innermost variant.
+///
+/// # Example
+/// ```
+///# use yew_router::Switch;
+///# use yew_router::route_table::RouteTable;
+/// #[derive(Switch)]
+/// enum AppRoute {
+///     #[to = "/about"]
+///     About,
+///     #[to = "/users{*:rest}"]
+///     Users(UserRoute),
+/// }
+///
+/// #[derive(Switch)]
+/// enum UserRoute {
+///     #[to = "/settings"]
+///     Settings,
+///     #[to = "/{id}"]
+///     Profile { id: usize },
+/// }
+///
+/// let table = RouteTable::of::<AppRoute>();
+/// let routes: Vec<String> = table.entries().iter().map(|entry| entry.matcher.to_string()).collect();
+/// assert_eq!(routes, vec!["/about", "/users/settings", "/users/{id}"]);
+///
+/// let sitemap = table.sitemap("https://example.com");
+/// assert!(sitemap.contains("<loc>https://example.com/users/settings</loc>"));
+/// assert!(!sitemap.contains("{id}"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RouteTable {
+    entries: Vec<RouteTableEntry>,
+}
+
+impl RouteTable {
+    /// Builds the table for a `Switch`, from its descriptions.
+    pub fn of<SW: Switch>() -> Self {
+        RouteTable::from_descriptions(&SW::describe())
+    }
+
+    /// Builds the table from the descriptions of a `Switch`.
+    pub fn from_descriptions(descriptions: &[SwitchDescription]) -> Self {
+        let mut entries = Vec::new();
+        add_entries(&mut entries, &[], None, &[], descriptions);
+        RouteTable { entries }
+    }
+
+    /// Gets the entries, in the order their variants are tried.
+    pub fn entries(&self) -> &[RouteTableEntry] {
+        &self.entries
+    }
+
+    /// Generates a `sitemap.xml` listing the routes that don't need any captures.
+    ///
+    /// Optional sections are left out of the listed routes.
+    ///
+    /// The base url is prepended to every route.
+    pub fn sitemap(&self, base_url: &str) -> String {
+        let base_url = base_url.trim_end_matches('/');
+        let mut listed = HashSet::new();
+        let mut sitemap = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+        );
+        for path in self.entries.iter().filter_map(RouteTableEntry::path) {
+            if listed.insert(path.clone()) {
+                sitemap.push_str("  <url><loc>");
+                push_escaped_xml(&mut sitemap, base_url);
+                push_escaped_xml(&mut sitemap, &path);
+                sitemap.push_str("</loc></url>\n");
+            }
+        }
+        sitemap.push_str("</urlset>\n");
+        sitemap
+    }
+
+    /// Generates a markdown table listing every route, the variants it produces, and its captures.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("| Route | Variant | Captures |\n| --- | --- | --- |\n");
+        for entry in &self.entries {
+            let captures: Vec<String> = entry
+                .capture_names
+                .iter()
+                .map(|name| format!("`{}`", name))
+                .collect();
+            markdown.push_str(&format!(
+                "| `{}` | {} | {} |\n",
+                // Pipes in allowed captures would otherwise end the cell.
+                entry.matcher.to_string().replace('|', "\\|"),
+                entry.variants.join(" > "),
+                captures.join(", ")
+            ));
+        }
+        markdown
+    }
+}
+
+/// Adds an entry for every innermost description.
+///
+/// If the descriptions belong to a nested `Switch`,
+/// `outer` holds the matcher of the enclosing entry and the name of the capture they take the place of.
+fn add_entries(
+    entries: &mut Vec<RouteTableEntry>,
+    variants: &[&'static str],
+    outer: Option<(&RouteMatcher, &str)>,
+    capture_names: &[String],
+    descriptions: &[SwitchDescription],
+) {
+    for description in descriptions {
+        let matcher = match outer {
+            Some((outer, rest)) => RouteMatcher {
+                tokens: substitute(&outer.tokens, rest, &description.matcher.tokens),
+                settings: outer.settings,
+            },
+            None => description.matcher.clone(),
+        };
+        let mut variants = variants.to_vec();
+        variants.push(description.variant);
+        let mut capture_names = capture_names.to_vec();
+        capture_names.extend(description.capture_names.iter().cloned());
+
+        match &description.rest {
+            Some(rest) if !description.children.is_empty() => {
+                capture_names.retain(|name| name != rest);
+                add_entries(
+                    entries,
+                    &variants,
+                    Some((&matcher, rest)),
+                    &capture_names,
+                    &description.children,
+                )
+            }
+            _ => entries.push(RouteTableEntry {
+                variants,
+                matcher,
+                capture_names,
+            }),
+        }
+    }
+}
+
+/// Replaces the capture named `rest` with the nested tokens.
+fn substitute(tokens: &[MatcherToken], rest: &str, nested: &[MatcherToken]) -> Vec<MatcherToken> {
+    let mut substituted = Vec::new();
+    for token in tokens {
+        match token {
+            MatcherToken::Capture(capture)
+                if capture.capture_variant == CaptureVariant::ManyNamed(rest.to_string()) =>
+            {
+                for nested_token in nested {
+                    push_token(&mut substituted, nested_token.clone());
+                }
+            }
+            MatcherToken::Optional(optional) => {
+                substituted.push(MatcherToken::Optional(substitute(optional, rest, nested)))
+            }
+            token => push_token(&mut substituted, token.clone()),
+        }
+    }
+    substituted
+}
+
+/// Pushes the token, merging it into the last token if they are both exact.
+fn push_token(tokens: &mut Vec<MatcherToken>, token: MatcherToken) {
+    if let (Some(MatcherToken::Exact(last)), MatcherToken::Exact(literal)) =
+        (tokens.last_mut(), &token)
+    {
+        last.push_str(literal);
+        return;
+    }
+    tokens.push(token)
+}
+
+fn push_escaped_xml(xml: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => xml.push_str("&amp;"),
+            '<' => xml.push_str("&lt;"),
+            '>' => xml.push_str("&gt;"),
+            '"' => xml.push_str("&quot;"),
+            '\'' => xml.push_str("&apos;"),
+            c => xml.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn description(
+        variant: &'static str,
+        matcher: &str,
+        rest: Option<&str>,
+        children: Vec<SwitchDescription>,
+    ) -> SwitchDescription {
+        let matcher = RouteMatcher::try_from(matcher).expect("should parse");
+        let mut capture_names: Vec<String> = matcher
+            .capture_names()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        capture_names.sort();
+        SwitchDescription {
+            variant,
+            matcher,
+            capture_names,
+            rest: rest.map(String::from),
+            children,
+        }
+    }
+
+    fn app_table() -> RouteTable {
+        RouteTable::from_descriptions(&[
+            description("Home", "/", None, vec![]),
+            description("About", "/about", None, vec![]),
+            description(
+                "Users",
+                "/users{*:rest}",
+                Some("rest"),
+                vec![
+                    description("Settings", "/settings", None, vec![]),
+                    description("Profile", "/{id}", None, vec![]),
+                ],
+            ),
+            description("Search", "/search/{kind(books|films)}", None, vec![]),
+        ])
+    }
+
+    #[test]
+    fn nested_switches_are_flattened() {
+        let table = app_table();
+        let entries: Vec<(String, Vec<&str>, Vec<String>)> = table
+            .entries()
+            .iter()
+            .map(|entry| {
+                (
+                    entry.matcher.to_string(),
+                    entry.variants.clone(),
+                    entry.capture_names.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("/".to_string(), vec!["Home"], vec![]),
+                ("/about".to_string(), vec!["About"], vec![]),
+                (
+                    "/users/settings".to_string(),
+                    vec!["Users", "Settings"],
+                    vec![]
+                ),
+                (
+                    "/users/{id}".to_string(),
+                    vec!["Users", "Profile"],
+                    vec!["id".to_string()]
+                ),
+                (
+                    "/search/{kind(books|films)}".to_string(),
+                    vec!["Search"],
+                    vec!["kind".to_string()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn rest_without_children_is_an_entry() {
+        let table = RouteTable::from_descriptions(&[description(
+            "Files",
+            "/files{*:path}",
+            None,
+            vec![],
+        )]);
+        assert_eq!(table.entries().len(), 1);
+        assert_eq!(table.entries()[0].capture_names, vec!["path".to_string()]);
+        assert_eq!(table.entries()[0].path(), None);
+    }
+
+    #[test]
+    fn optional_sections_are_left_out_of_paths() {
+        let table =
+            RouteTable::from_descriptions(&[description("Users", "/users[/{id}]", None, vec![])]);
+        assert_eq!(table.entries()[0].path(), Some("/users".to_string()));
+    }
+
+    #[test]
+    fn sitemap_lists_parameterless_routes() {
+        let sitemap = app_table().sitemap("https://example.com/a&b/");
+        assert_eq!(
+            sitemap,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n  \
+             <url><loc>https://example.com/a&amp;b/</loc></url>\n  \
+             <url><loc>https://example.com/a&amp;b/about</loc></url>\n  \
+             <url><loc>https://example.com/a&amp;b/users/settings</loc></url>\n\
+             </urlset>\n"
+        );
+    }
+
+    #[test]
+    fn sitemap_routes_are_encoded() {
+        let table = RouteTable::from_descriptions(&[description(
+            "Report",
+            "/café/report\\(1\\).pdf",
+            None,
+            vec![],
+        )]);
+        assert!(table
+            .sitemap("https://example.com")
+            .contains("<loc>https://example.com/caf%C3%A9/report%281%29.pdf</loc>"));
+    }
+
+    #[test]
+    fn sitemap_lists_routes_once() {
+        let table = RouteTable::from_descriptions(&[
+            description("About", "/about", None, vec![]),
+            description("AboutUs", "/about", None, vec![]),
+        ]);
+        assert_eq!(table.sitemap("").matches("<url>").count(), 1);
+    }
+
+    #[test]
+    fn markdown() {
+        assert_eq!(
+            app_table().to_markdown(),
+            "| Route | Variant | Captures |\n\
+             | --- | --- | --- |\n\
+             | `/` | Home |  |\n\
+             | `/about` | About |  |\n\
+             | `/users/settings` | Users > Settings |  |\n\
+             | `/users/{id}` | Users > Profile | `id` |\n\
+             | `/search/{kind(books\\|films)}` | Search | `kind` |\n"
+        );
+    }
+}
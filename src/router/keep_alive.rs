@@ -0,0 +1,244 @@
+//! Keeping the views of previously matched routes mounted.
+use crate::router::SwitchEq;
+use std::fmt::{self, Debug, Formatter};
+
+/// Makes the `Router` keep the views of the most recently matched `Switch` values mounted,
+/// but hidden, instead of rebuilding them when they are matched again.
+///
+/// This keeps the state of their components, like scroll positions and form inputs.
+/// Views are identified by a copy of the `Switch` value they were rendered for,
+/// and once more than `capacity` views are kept, the least recently shown one is removed.
+pub struct KeepAlive<SW> {
+    capacity: usize,
+    switch_eq: SwitchEq<SW>,
+    copy: fn(&SW) -> SW,
+}
+
+impl<SW: PartialEq + Clone + 'static> KeepAlive<SW> {
+    /// Keeps up to `capacity` views mounted.
+    pub fn new(capacity: usize) -> Self {
+        KeepAlive::with_switch_eq(capacity, SwitchEq::new())
+    }
+}
+
+impl<SW: Clone> KeepAlive<SW> {
+    /// Keeps up to `capacity` views mounted,
+    /// identifying them by the `Switch` values that the `SwitchEq` considers the same.
+    pub fn with_switch_eq(capacity: usize, switch_eq: SwitchEq<SW>) -> Self {
+        KeepAlive {
+            capacity,
+            switch_eq,
+            copy: SW::clone,
+        }
+    }
+}
+
+impl<SW> KeepAlive<SW> {
+    /// Gets the number of views that are kept mounted.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Determines if the views for the `Switch` values are the same view.
+    pub(crate) fn is_same(&self, a: &SW, b: &SW) -> bool {
+        self.switch_eq.same(a, b)
+    }
+
+    /// Copies the `Switch` value, to identify its view.
+    pub(crate) fn key(&self, switch: &SW) -> SW {
+        (self.copy)(switch)
+    }
+}
+
+impl<SW> Clone for KeepAlive<SW> {
+    fn clone(&self) -> Self {
+        KeepAlive {
+            capacity: self.capacity,
+            switch_eq: self.switch_eq.clone(),
+            copy: self.copy,
+        }
+    }
+}
+
+impl<SW> Debug for KeepAlive<SW> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("KeepAlive")
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+/// A least recently used cache of views.
+///
+/// Entries never move, so views that are rendered in the order of the entries
+/// keep their positions in the DOM, and their components aren't rebuilt.
+/// When the cache is full, the least recently used entry is evicted, leaving its slot empty,
+/// so its view is removed instead of being reused for the view of another route.
+/// The inserted entry takes the slot that was left empty by the eviction before,
+/// which has been rendered empty since, or a new slot.
+#[derive(Debug)]
+pub(crate) struct ViewCache<K, V> {
+    /// The slots of the entries, which are empty once their entries are evicted.
+    slots: Vec<Option<CacheEntry<K, V>>>,
+    /// Incremented whenever an entry is used.
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry<K, V> {
+    key: K,
+    value: V,
+    last_used: u64,
+}
+
+impl<K, V> ViewCache<K, V> {
+    pub(crate) fn new() -> Self {
+        ViewCache {
+            slots: Vec::new(),
+            clock: 0,
+        }
+    }
+
+    /// Finds the index of the entry for the key.
+    pub(crate) fn find(&self, key: &K, is_same: impl Fn(&K, &K) -> bool) -> Option<usize> {
+        self.slots
+            .iter()
+            .position(|slot| slot.iter().any(|entry| is_same(&entry.key, key)))
+    }
+
+    /// Marks the entry for the key as the most recently used, inserting it if it isn't cached,
+    /// and returns its index.
+    ///
+    /// If the entry is cached, its key is replaced, so it holds the most recently used key.
+    ///
+    /// If an entry has to be inserted when the cache already holds `capacity` entries,
+    /// the least recently used entry is evicted, and its slot is left empty.
+    pub(crate) fn touch(
+        &mut self,
+        key: K,
+        value: impl FnOnce() -> V,
+        capacity: usize,
+        is_same: impl Fn(&K, &K) -> bool,
+    ) -> usize {
+        self.clock += 1;
+        let last_used = self.clock;
+        if let Some(index) = self.find(&key, is_same) {
+            let entry = self.slots[index].as_mut().expect("Found entries are present");
+            entry.key = key;
+            entry.last_used = last_used;
+            return index;
+        }
+        let entry = CacheEntry {
+            key,
+            value: value(),
+            last_used,
+        };
+        let empty = self.slots.iter().position(Option::is_none);
+        if self.len() >= capacity.max(1) {
+            let (evicted, _) = self
+                .slots
+                .iter()
+                .enumerate()
+                .filter_map(|(index, slot)| slot.as_ref().map(|entry| (index, entry.last_used)))
+                .min_by_key(|(_, last_used)| *last_used)
+                .expect("The cache holds at least one entry");
+            self.slots[evicted] = None;
+        }
+        match empty {
+            Some(index) => {
+                self.slots[index] = Some(entry);
+                index
+            }
+            None => {
+                self.slots.push(Some(entry));
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    /// Gets the value of the entry at the index.
+    pub(crate) fn get_mut(&mut self, index: usize) -> &mut V {
+        &mut self.slots[index]
+            .as_mut()
+            .expect("Indexes of entries are only given out while they are present")
+            .value
+    }
+
+    /// Gets the number of entries.
+    pub(crate) fn len(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    /// Iterates over the keys and values of the entries in the order of their slots,
+    /// including the empty ones.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = Option<(&K, &V)>> {
+        self.slots
+            .iter()
+            .map(|slot| slot.as_ref().map(|entry| (&entry.key, &entry.value)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn touch(cache: &mut ViewCache<&'static str, ()>, key: &'static str) -> usize {
+        cache.touch(key, || (), 2, |a, b| a == b)
+    }
+
+    fn keys(cache: &ViewCache<&'static str, ()>) -> Vec<Option<&'static str>> {
+        cache.iter().map(|entry| entry.map(|(key, _)| *key)).collect()
+    }
+
+    #[test]
+    fn entries_keep_their_positions() {
+        let mut cache = ViewCache::new();
+        assert_eq!(touch(&mut cache, "lorem"), 0);
+        assert_eq!(touch(&mut cache, "ipsum"), 1);
+        assert_eq!(touch(&mut cache, "lorem"), 0);
+        assert_eq!(keys(&cache), vec![Some("lorem"), Some("ipsum")]);
+    }
+
+    #[test]
+    fn least_recently_used_is_evicted() {
+        let mut cache = ViewCache::new();
+        touch(&mut cache, "lorem");
+        touch(&mut cache, "ipsum");
+        touch(&mut cache, "lorem");
+        assert_eq!(touch(&mut cache, "dolor"), 2);
+        assert_eq!(keys(&cache), vec![Some("lorem"), None, Some("dolor")]);
+        assert_eq!(touch(&mut cache, "sit"), 1);
+        assert_eq!(keys(&cache), vec![None, Some("sit"), Some("dolor")]);
+        assert_eq!(touch(&mut cache, "amet"), 0);
+        assert_eq!(keys(&cache), vec![Some("amet"), Some("sit"), None]);
+    }
+
+    #[test]
+    fn evicted_views_are_not_reused() {
+        let mut cache = ViewCache::new();
+        let mut views = 0;
+        let mut view = || {
+            views += 1;
+            views
+        };
+        for key in &["lorem", "ipsum", "dolor"] {
+            let index = cache.touch(*key, &mut view, 2, |a, b| a == b);
+            *cache.get_mut(index) *= 10;
+        }
+        let evicted = cache.find(&"lorem", |a, b| a == b);
+        assert_eq!(evicted, None);
+        let index = cache.find(&"dolor", |a, b| a == b).expect("dolor is cached");
+        assert_ne!(index, 0);
+        assert_eq!(*cache.get_mut(index), 30);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn keys_are_replaced_by_the_most_recent_one() {
+        let mut cache = ViewCache::new();
+        let same_length = |a: &&str, b: &&str| a.len() == b.len();
+        cache.touch("lorem", || (), 2, same_length);
+        assert_eq!(cache.touch("ipsum", || (), 2, same_length), 0);
+        assert_eq!(keys(&cache), vec![Some("ipsum")]);
+    }
+}
@@ -0,0 +1,213 @@
+//! Loading of the data that routes need before they are rendered.
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt::{self, Debug, Formatter};
+use std::rc::Rc;
+use yew::services::Task;
+use yew::Callback;
+
+/// Loads the data that a route needs before it is rendered by the `Router`.
+///
+/// # Example
+/// ```
+///# use yew_router::Switch;
+///# use yew_router::router::Loader;
+///# use yew::services::Task;
+///# #[derive(Switch)]
+///# enum S {
+///#     #[to = "/users/{id}"]
+///#     User { id: usize },
+///#     #[to = "/"]
+///#     Home,
+///# }
+/// let loader = Loader::new(|switch: &S, callback| -> Option<Box<dyn Task>> {
+///     match switch {
+///         S::User { id } => {
+///             // Start fetching the user, and call the callback with them once they arrive.
+///             // Returning the FetchTask allows it to be cancelled if the route changes again.
+///             callback.emit(format!("User {}", id));
+///             None
+///         }
+///         S::Home => {
+///             callback.emit(String::new());
+///             None
+///         }
+///     }
+/// });
+/// ```
+pub struct Loader<SW, D>(Rc<dyn Fn(&SW, Callback<D>) -> Option<Box<dyn Task>>>);
+
+impl<SW, D> Loader<SW, D> {
+    /// Creates a loader from a function that starts loading the data for a route,
+    /// and calls the callback with the data once it has loaded.
+    ///
+    /// The function may return the `Task` that loads the data, like a `FetchTask`,
+    /// which is cancelled if the route changes before the data has loaded.
+    pub fn new<F>(load: F) -> Self
+    where
+        F: Fn(&SW, Callback<D>) -> Option<Box<dyn Task>> + 'static,
+    {
+        Loader(Rc::new(load))
+    }
+
+    /// Starts loading the data for the route.
+    pub(crate) fn load(&self, switch: &SW, callback: Callback<D>) -> Option<Box<dyn Task>> {
+        (self.0)(switch, callback)
+    }
+}
+
+impl<SW, E> Loader<SW, Result<(), E>> {
+    /// Creates a loader that loads each group of routes once,
+    /// the first time that one of its routes is matched.
+    ///
+    /// `group` gets the name of the group that a route belongs to,
+    /// or `None` if the route doesn't need anything to be loaded.
+    /// `load` starts loading a group, like by asking JavaScript to fetch the resources that
+    /// infrequently visited pages need, and calls the callback once it has loaded or failed.
+    /// Groups that failed to load are loaded again the next time one of their routes is matched.
+    ///
+    /// While a group is loading, the `Router` renders the route with its `render` function,
+    /// which can show a placeholder.
+    /// Once it has loaded, the route is rendered with the `render_loaded` function,
+    /// which is given the error if loading failed.
+    ///
+    /// # Note
+    /// All components are compiled into the same wasm module,
+    /// so this can't split their code into separately loaded modules by itself.
+    ///
+    /// # Example
+    /// ```
+    ///# use yew_router::Switch;
+    ///# use yew_router::router::Loader;
+    ///# #[derive(Switch)]
+    ///# enum S {
+    ///#     #[to = "/admin/{*:rest}"]
+    ///#     Admin { rest: String },
+    ///#     #[to = "/"]
+    ///#     Home,
+    ///# }
+    /// let loader: Loader<S, Result<(), String>> = Loader::lazy(
+    ///     |switch: &S| match switch {
+    ///         S::Admin { .. } => Some("admin"),
+    ///         S::Home => None,
+    ///     },
+    ///     |group, callback| {
+    ///         // Start loading the group, and call the callback once it has loaded.
+    ///         callback.emit(Ok(()));
+    ///         None
+    ///     },
+    /// );
+    /// ```
+    pub fn lazy<G, F>(group: G, load: F) -> Self
+    where
+        G: Fn(&SW) -> Option<&'static str> + 'static,
+        F: Fn(&'static str, Callback<Result<(), E>>) -> Option<Box<dyn Task>> + 'static,
+        E: 'static,
+    {
+        let loaded: Rc<RefCell<HashSet<&'static str>>> = Rc::new(RefCell::new(HashSet::new()));
+        Loader::new(move |switch: &SW, callback: Callback<Result<(), E>>| {
+            let group = match group(switch) {
+                Some(group) if !loaded.borrow().contains(group) => group,
+                _ => {
+                    callback.emit(Ok(()));
+                    return None;
+                }
+            };
+            let loaded = loaded.clone();
+            let callback = Callback::from(move |result: Result<(), E>| {
+                if result.is_ok() {
+                    loaded.borrow_mut().insert(group);
+                }
+                callback.emit(result)
+            });
+            load(group, callback)
+        })
+    }
+}
+
+impl<SW, D> Clone for Loader<SW, D> {
+    fn clone(&self) -> Self {
+        Loader(self.0.clone())
+    }
+}
+
+impl<SW, D> Debug for Loader<SW, D> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Loader").finish()
+    }
+}
+
+/// What the `Router` renders while the data for a new route is loading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadMode {
+    /// The new route is rendered by the `render` function, as a pending view,
+    /// until its data has loaded.
+    Pending,
+    /// The previous route and its data are kept until the data for the new route has loaded,
+    /// deferring the navigation.
+    ///
+    /// If no data has loaded yet, the new route is rendered as a pending view instead.
+    Defer,
+}
+
+impl Default for LoadMode {
+    fn default() -> Self {
+        LoadMode::Pending
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+
+    type LazyLoader = Loader<&'static str, Result<(), ()>>;
+
+    /// Creates a loader for the `admin` group, which fails to load while `fail` is set,
+    /// and counts the times it is loaded.
+    fn admin_loader(fail: Rc<Cell<bool>>, loads: Rc<Cell<usize>>) -> LazyLoader {
+        Loader::lazy(
+            |route: &&'static str| {
+                if route.starts_with("/admin") {
+                    Some("admin")
+                } else {
+                    None
+                }
+            },
+            move |_group, callback| {
+                loads.set(loads.get() + 1);
+                callback.emit(if fail.get() { Err(()) } else { Ok(()) });
+                None
+            },
+        )
+    }
+
+    fn load(loader: &LazyLoader, route: &'static str) -> Result<(), ()> {
+        let result = Rc::new(Cell::new(None));
+        let loaded = result.clone();
+        loader.load(&route, Callback::from(move |load_result| loaded.set(Some(load_result))));
+        result.get().expect("should have loaded")
+    }
+
+    #[test]
+    fn groups_are_loaded_once() {
+        let loads = Rc::new(Cell::new(0));
+        let loader = admin_loader(Rc::new(Cell::new(false)), loads.clone());
+        assert_eq!(load(&loader, "/admin/users"), Ok(()));
+        assert_eq!(load(&loader, "/admin/settings"), Ok(()));
+        assert_eq!(load(&loader, "/"), Ok(()));
+        assert_eq!(loads.get(), 1);
+    }
+
+    #[test]
+    fn groups_that_failed_are_loaded_again() {
+        let fail = Rc::new(Cell::new(true));
+        let loads = Rc::new(Cell::new(0));
+        let loader = admin_loader(fail.clone(), loads.clone());
+        assert_eq!(load(&loader, "/admin/users"), Err(()));
+        fail.set(false);
+        assert_eq!(load(&loader, "/admin/users"), Ok(()));
+        assert_eq!(load(&loader, "/admin/users"), Ok(()));
+        assert_eq!(loads.get(), 2);
+    }
+}
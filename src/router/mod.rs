@@ -1,11 +1,14 @@
 //! Router component and related types.
+mod keep_alive;
+mod loader;
 mod router;
+mod switch_eq;
+mod transition;
 
-pub use router::{Props, Render, Router};
+pub use keep_alive::KeepAlive;
+pub use loader::{LoadMode, Loader};
+pub use router::{Props, Render, RenderLoaded, Router};
+pub use switch_eq::SwitchEq;
+pub use transition::{Transition, TransitionDirection};
 
-use crate::agent::AgentState;
-
-/// Any state that can be managed by the `Router` must meet the criteria of this trait.
-pub trait RouterState<'de>: AgentState<'de> + PartialEq {}
-
-impl<'de, T> RouterState<'de> for T where T: AgentState<'de> + PartialEq {}
+pub use crate::route::RouterState;
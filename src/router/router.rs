@@ -1,12 +1,25 @@
 //! Router Component.
 
+#[cfg(feature = "agent")]
 use crate::agent::{RouteAgentBridge, RouteRequest};
-use crate::route::Route;
-use crate::router::RouterState;
+use crate::route::{Route, RouteChange};
+use crate::router::keep_alive::ViewCache;
+use crate::router::{
+    KeepAlive,
+    LoadMode,
+    Loader,
+    RouterState,
+    SwitchEq,
+    Transition,
+    TransitionDirection,
+};
 use crate::Switch;
 use std::fmt::{self, Debug, Error as FmtError, Formatter};
+use std::mem;
 use std::rc::Rc;
 use yew::{
+    html,
+    services::{timeout::TimeoutTask, Task, TimeoutService},
     virtual_dom::VNode,
     Callback,
     Component,
@@ -21,6 +34,25 @@ use yew::{
 /// Based on the current url and its child [Routes](struct.Route.html), it will choose one route and
 /// render its associated component.
 ///
+/// If a `route` is provided, it will be rendered instead of the browser's current route.
+/// The Router won't connect to the `RouteAgent` then, so it can be rendered without a browser.
+/// Without the "agent" feature, the Router only renders the provided `route`.
+///
+/// If a `loader` is provided, the data that a route needs is loaded when the route changes,
+/// and the route is rendered with it by the `render_loaded` function once it has loaded.
+/// Loads that haven't finished when the route changes again are cancelled.
+///
+/// If `keep_alive` is provided, the views of the most recently matched `Switch` values stay mounted,
+/// but hidden, so their components keep their state when their routes are matched again.
+/// Kept views also keep their loaded data, so it isn't loaded again.
+///
+/// If a `transition` is provided, the view of the previous route stays mounted while the route
+/// changes, and both views are wrapped in elements with classes that can animate the change.
+///
+/// If a `switch_eq` is provided, route changes that switch to the same value don't re-render the
+/// Router. Changes of its props only re-render it if its render functions were recreated,
+/// so they should be created once, rather than in every `view` of the parent.
+///
 ///
 /// # Example
 /// ```
@@ -66,22 +98,87 @@ use yew::{
 ///     Variant
 /// }
 /// ```
-#[derive(Debug)]
 pub struct Router<
     T: for<'de> RouterState<'de>,
     SW: Switch + 'static,
     M: 'static,
+    D: 'static = (),
 > {
-    route:        Route<T>,
-    props:        Props<T, SW, M>,
-    router_agent: RouteAgentBridge<T>,
+    route:           Route<T>,
+    /// The value that the route switched to.
+    switch:          Option<SW>,
+    props:           Props<T, SW, M, D>,
+    #[cfg(feature = "agent")]
+    router_agent:    Option<RouteAgentBridge<T>>,
+    link:            ComponentLink<Self>,
+    /// The data loaded for the route.
+    data:            Option<D>,
+    /// The route that will replace the current one once its data has loaded,
+    /// and the value it switched to.
+    pending_route:   Option<(Route<T>, Option<SW>)>,
+    /// Incremented for every load, so data from stale loads can be discarded.
+    load_generation: u64,
+    load_task:       Option<Box<dyn Task>>,
+    /// The data of the views that are kept mounted, if `keep_alive` is provided,
+    /// keyed by the values they were rendered for.
+    ///
+    /// The view of the current route holds no data, as it is kept in `data`.
+    kept:            ViewCache<SW, Option<D>>,
+    /// The direction of the transition to the next route that is displayed,
+    /// or `None` if it shouldn't be transitioned to.
+    direction:       Option<TransitionDirection>,
+    /// The view of the previous route, while it is transitioned away from.
+    leaving:         Option<LeavingView<T, SW, D>>,
+    transition_task: Option<TimeoutTask>,
+    timeout_service: TimeoutService,
+    /// Determines if the view of the current route is rendered before the leaving view.
+    ///
+    /// It alternates between transitions, so the views keep their places in the DOM,
+    /// and their components aren't rebuilt.
+    current_first:   bool,
 }
 
-impl<T, SW, M> Router<T, SW, M>
+/// The view of the previous route, while it is transitioned away from.
+#[derive(Debug)]
+struct LeavingView<T, SW, D> {
+    route:      Route<T>,
+    /// The value that the route switched to.
+    switch:     Option<SW>,
+    /// The data of the route, unless its view is kept.
+    data:       Option<D>,
+    /// The index of its view, if it is kept.
+    kept_index: Option<usize>,
+    direction:  TransitionDirection,
+}
+
+impl<T, SW, M, D> Debug for Router<T, SW, M, D>
 where
     T: for<'de> RouterState<'de>,
     SW: Switch + 'static,
     M: 'static,
+    D: 'static,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut f = f.debug_struct("Router");
+        f.field("route", &self.route).field("props", &self.props);
+        #[cfg(feature = "agent")]
+        f.field("router_agent", &self.router_agent);
+        f.field("loaded", &self.data.is_some())
+            .field("pending_route", &self.pending_route.as_ref().map(|(route, _)| route))
+            .field("load_generation", &self.load_generation)
+            .field("kept_views", &self.kept.len())
+            .field("direction", &self.direction)
+            .field("leaving", &self.leaving.as_ref().map(|leaving| &leaving.route))
+            .finish()
+    }
+}
+
+impl<T, SW, M, D> Router<T, SW, M, D>
+where
+    T: for<'de> RouterState<'de>,
+    SW: Switch + 'static,
+    M: 'static,
+    D: 'static,
 {
     /// Wrap a render closure so that it can be used by the Router.
     /// # Example
@@ -105,23 +202,217 @@ where
     /// });
     ///# }
     /// ```
-    pub fn render<F: RenderFn<Router<T, SW, M>, SW> + 'static>(
+    pub fn render<F: RenderFn<Router<T, SW, M, D>, SW> + 'static>(
         f: F,
-    ) -> Render<T, SW, M> {
+    ) -> Render<T, SW, M, D> {
         Render::new(f)
     }
+
+    /// Wrap a render closure for routes whose data has been loaded,
+    /// so that it can be used by the Router.
+    pub fn render_loaded<F: RenderLoadedFn<Router<T, SW, M, D>, SW, D> + 'static>(
+        f: F,
+    ) -> RenderLoaded<T, SW, M, D> {
+        RenderLoaded(Rc::new(f))
+    }
+
+    /// Changes the route, loading its data if there is a loader.
+    ///
+    /// The route is displayed with a transition in the direction, if there is one.
+    /// If the route is ignored, because it wouldn't change the view, the direction is dropped,
+    /// so it doesn't apply to the next route that is displayed.
+    fn change_route(
+        &mut self,
+        route: Route<T>,
+        direction: Option<TransitionDirection>,
+    ) -> ShouldRender {
+        let switch = SW::switch(route.clone());
+        if self.switches_like_target(switch.as_ref()) {
+            // The view wouldn't change.
+            match &mut self.pending_route {
+                Some(pending_route) => *pending_route = (route, switch),
+                None => {
+                    self.route = route;
+                    self.switch = switch;
+                }
+            }
+            return false;
+        }
+        let loader: Loader<SW, D> = match &self.props.loader {
+            Some(loader) => loader.clone(),
+            None => {
+                if self.route == route {
+                    return false;
+                }
+                self.direction = direction;
+                self.display(route, switch, None);
+                return true;
+            }
+        };
+        // The route that is either rendered, or waiting for its data to be rendered.
+        let target = self
+            .pending_route
+            .as_ref()
+            .map_or(&self.route, |(pending_route, _)| pending_route);
+        if self.load_generation > 0 && *target == route {
+            return false;
+        }
+
+        self.direction = direction;
+        self.cancel_load();
+        self.load_generation += 1;
+        if let Some(data) = self.take_kept_data(switch.as_ref()) {
+            self.pending_route = None;
+            self.display(route, switch, Some(data));
+            return true;
+        }
+        let generation = self.load_generation;
+        if let Some(switch) = &switch {
+            let callback = self
+                .link
+                .send_back(move |data| Msg::Loaded(generation, data));
+            self.load_task = loader.load(switch, callback);
+        }
+
+        if switch.is_some() && self.props.load_mode == LoadMode::Defer && self.data.is_some() {
+            self.pending_route = Some((route, switch));
+            false
+        } else {
+            self.display(route, switch, None);
+            self.pending_route = None;
+            true
+        }
+    }
+
+    /// Determines if the value that a route switched to is the same as the value of the route
+    /// that is either rendered, or waiting for its data to be rendered, if there is a `switch_eq`.
+    fn switches_like_target(&self, switch: Option<&SW>) -> bool {
+        let switch_eq = match &self.props.switch_eq {
+            Some(switch_eq) => switch_eq,
+            None => return false,
+        };
+        let target_switch = match &self.pending_route {
+            Some((_, pending_switch)) => pending_switch,
+            None => &self.switch,
+        };
+        switch_eq.same_option(switch, target_switch.as_ref())
+    }
+
+    /// Shows the route, which switched to the value, with its data.
+    ///
+    /// If views are kept alive, the view that is replaced is kept with its data.
+    /// If there is a transition, it is started.
+    fn display(&mut self, route: Route<T>, switch: Option<SW>, data: Option<D>) {
+        let previous_switch = mem::replace(&mut self.switch, switch);
+        let previous_route = mem::replace(&mut self.route, route);
+        let mut previous_data = mem::replace(&mut self.data, data);
+        let mut kept_index = None;
+        if let Some(keep_alive) = &self.props.keep_alive {
+            let is_same = |a: &SW, b: &SW| keep_alive.is_same(a, b);
+            if let Some(switch) = &self.switch {
+                let key = keep_alive.key(switch);
+                self.kept.touch(key, || None, keep_alive.capacity(), is_same);
+            }
+            // The previous view is looked up after the current one is kept,
+            // in case keeping it evicted the previous one.
+            kept_index = previous_switch
+                .as_ref()
+                .and_then(|switch| self.kept.find(switch, is_same));
+            if let Some(index) = kept_index {
+                *self.kept.get_mut(index) = previous_data.take();
+            }
+        }
+
+        let (duration, direction) = match (&self.props.transition, self.direction.take()) {
+            (Some(transition), Some(direction)) if previous_route != self.route => {
+                (transition.duration, direction)
+            }
+            _ => {
+                self.leaving = None;
+                self.transition_task = None;
+                return;
+            }
+        };
+        let callback = self.link.send_back(|_| Msg::TransitionEnded);
+        self.transition_task = Some(self.timeout_service.spawn(duration, callback));
+        self.current_first = !self.current_first;
+        self.leaving = Some(LeavingView {
+            route: previous_route,
+            switch: previous_switch,
+            data: previous_data,
+            kept_index,
+            direction,
+        });
+    }
+
+    /// Takes the data out of the kept view for the value, if there is one.
+    fn take_kept_data(&mut self, switch: Option<&SW>) -> Option<D> {
+        let keep_alive = self.props.keep_alive.as_ref()?;
+        let index = self
+            .kept
+            .find(switch?, |a, b| keep_alive.is_same(a, b))?;
+        self.kept.get_mut(index).take()
+    }
+
+    /// Renders the value that a route switched to with its data.
+    fn render_switch(&self, switch: Option<&SW>, data: Option<&D>) -> Html<Self> {
+        match (data, &self.props.render_loaded) {
+            (Some(data), Some(render_loaded)) => (&render_loaded.0)(switch, data),
+            _ => (&self.props.render.0)(switch),
+        }
+    }
+
+    /// Renders the current route, in an element with the classes of the transition to it.
+    fn render_current(&self) -> Html<Self> {
+        let classes = match (&self.props.transition, &self.leaving) {
+            (Some(transition), Some(leaving)) => transition.enter_classes(leaving.direction),
+            _ => String::new(),
+        };
+        let view = self.render_switch(self.switch.as_ref(), self.data.as_ref());
+        html! {
+            <div class=classes>{ view }</div>
+        }
+    }
+
+    /// Renders the value of the leaving view, in an element with the classes of the transition.
+    fn render_leaving(
+        &self,
+        leaving: &LeavingView<T, SW, D>,
+        switch: Option<&SW>,
+        data: Option<&D>,
+    ) -> Html<Self> {
+        let classes = match &self.props.transition {
+            Some(transition) => transition.leave_classes(leaving.direction),
+            None => String::new(),
+        };
+        html! {
+            <div class=classes>{ self.render_switch(switch, data) }</div>
+        }
+    }
+
+    fn cancel_load(&mut self) {
+        if let Some(mut task) = self.load_task.take() {
+            if task.is_active() {
+                task.cancel();
+            }
+        }
+    }
 }
 
 /// Message for Router.
 #[derive(Debug, Clone)]
-pub enum Msg<T, M> {
+pub enum Msg<T, M, D = ()> {
     /// Updates the route
-    UpdateRoute(Route<T>),
+    UpdateRoute(RouteChange<T>),
     /// Inner message
     InnerMessage(M),
+    /// The data for the route was loaded by the load with the generation.
+    Loaded(u64, D),
+    /// The transition to the current route has ended.
+    TransitionEnded,
 }
 
-impl<T, M> From<M> for Msg<T, M> {
+impl<T, M, D> From<M> for Msg<T, M, D> {
     fn from(inner: M) -> Self {
         Msg::InnerMessage(inner)
     }
@@ -129,9 +420,12 @@ impl<T, M> From<M> for Msg<T, M> {
 
 // TODO consider removing the Option, and creating two different render functions - one for rendering the switch, and one for a 404 case.
 /// Render function definition
-pub trait RenderFn<CTX: Component, SW>: Fn(Option<SW>) -> Html<CTX> {}
+///
+/// It is called with a reference to the value that the route switched to, which the `Router`
+/// keeps between renders, or `None` if the route didn't switch to a value.
+pub trait RenderFn<CTX: Component, SW>: Fn(Option<&SW>) -> Html<CTX> {}
 impl<T, CTX: Component, SW> RenderFn<CTX, SW> for T where
-    T: Fn(Option<SW>) -> Html<CTX>
+    T: Fn(Option<&SW>) -> Html<CTX>
 {
 }
 /// Owned Render function.
@@ -139,67 +433,186 @@ pub struct Render<
     T: for<'de> RouterState<'de>,
     SW: Switch + 'static,
     M: 'static,
->(pub(crate) Rc<dyn RenderFn<Router<T, SW, M>, SW>>);
-impl<T: for<'de> RouterState<'de>, SW: Switch, M> Render<T, SW, M> {
+    D: 'static = (),
+>(pub(crate) Rc<dyn RenderFn<Router<T, SW, M, D>, SW>>);
+impl<T: for<'de> RouterState<'de>, SW: Switch, M, D> Render<T, SW, M, D> {
     /// New render function
-    fn new<F: RenderFn<Router<T, SW, M>, SW> + 'static>(f: F) -> Self {
+    fn new<F: RenderFn<Router<T, SW, M, D>, SW> + 'static>(f: F) -> Self {
         Render(Rc::new(f))
     }
 }
-impl<T: for<'de> RouterState<'de>, SW: Switch, M> Debug for Render<T, SW, M> {
+impl<T: for<'de> RouterState<'de>, SW: Switch, M, D> Debug for Render<T, SW, M, D> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("Render2").finish()
     }
 }
 
+/// Render function definition for routes whose data has been loaded.
+pub trait RenderLoadedFn<CTX: Component, SW, D>: Fn(Option<&SW>, &D) -> Html<CTX> {}
+impl<T, CTX: Component, SW, D> RenderLoadedFn<CTX, SW, D> for T where
+    T: Fn(Option<&SW>, &D) -> Html<CTX>
+{
+}
+/// Owned Render function for routes whose data has been loaded.
+pub struct RenderLoaded<
+    T: for<'de> RouterState<'de>,
+    SW: Switch + 'static,
+    M: 'static,
+    D: 'static = (),
+>(pub(crate) Rc<dyn RenderLoadedFn<Router<T, SW, M, D>, SW, D>>);
+impl<T: for<'de> RouterState<'de>, SW: Switch, M, D> Debug for RenderLoaded<T, SW, M, D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderLoaded").finish()
+    }
+}
+
 /// Properties for Router.
 #[derive(Properties)]
-pub struct Props<T: for<'de> RouterState<'de>, SW: Switch + 'static, M: 'static>
-{
+pub struct Props<
+    T: for<'de> RouterState<'de>,
+    SW: Switch + 'static,
+    M: 'static,
+    D: 'static,
+> {
     /// Render fn
+    ///
+    /// If there is a loader, this renders routes whose data hasn't loaded yet.
+    ///
+    /// The Router is re-rendered when it is given a different render fn,
+    /// so it should be created once, rather than in every `view` of the parent.
     #[props(required)]
-    pub render: Render<T, SW, M>,
+    pub render: Render<T, SW, M, D>,
     /// Optional Callback for propagating messages to parent components.
     pub callback: Option<Callback<M>>,
+    /// Optional route to render instead of the browser's current route.
+    ///
+    /// This allows the Router to be rendered outside of a browser, like when prerendering pages.
+    pub route: Option<Route<T>>,
+    /// Optional loader for the data that routes need.
+    pub loader: Option<Loader<SW, D>>,
+    /// What is rendered while the data for a new route is loading.
+    pub load_mode: LoadMode,
+    /// Render fn for routes whose data has loaded.
+    ///
+    /// If it isn't provided, the `render` function is used instead.
+    pub render_loaded: Option<RenderLoaded<T, SW, M, D>>,
+    /// Optionally keeps the views of previously matched routes mounted.
+    pub keep_alive: Option<KeepAlive<SW>>,
+    /// Optional transition between the views of routes.
+    pub transition: Option<Transition>,
+    /// Optionally compares the values that routes switch to,
+    /// so that the Router isn't re-rendered when the route changes, but its value doesn't.
+    pub switch_eq: Option<SwitchEq<SW>>,
 }
 
-impl<T: for<'de> RouterState<'de>, SW: Switch, M> Debug for Props<T, SW, M> {
+impl<T, SW, M, D> Props<T, SW, M, D>
+where
+    T: for<'de> RouterState<'de>,
+    SW: Switch + 'static,
+    M: 'static,
+    D: 'static,
+{
+    /// Determines if the props render routes the same way.
+    ///
+    /// Render functions are compared by pointer.
+    fn renders_like(&self, other: &Self) -> bool {
+        let render_loaded_is_same = match (&self.render_loaded, &other.render_loaded) {
+            (Some(a), Some(b)) => Rc::ptr_eq(&a.0, &b.0),
+            (None, None) => true,
+            _ => false,
+        };
+        Rc::ptr_eq(&self.render.0, &other.render.0)
+            && render_loaded_is_same
+            && self.keep_alive.as_ref().map(KeepAlive::capacity)
+                == other.keep_alive.as_ref().map(KeepAlive::capacity)
+            && self.transition == other.transition
+    }
+}
+
+impl<T: for<'de> RouterState<'de>, SW: Switch, M, D> Debug for Props<T, SW, M, D> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), FmtError> {
         f.debug_struct("Props").finish()
     }
 }
 
-impl<T, SW, M> Component for Router<T, SW, M>
+impl<T, SW, M, D> Component for Router<T, SW, M, D>
 where
     T: for<'de> RouterState<'de>,
     SW: Switch + 'static,
     M: 'static,
+    D: 'static,
 {
-    type Message = Msg<T, M>;
-    type Properties = Props<T, SW, M>;
+    type Message = Msg<T, M, D>;
+    type Properties = Props<T, SW, M, D>;
 
+    #[cfg_attr(not(feature = "agent"), allow(unused_mut))]
     fn create(props: Self::Properties, mut link: ComponentLink<Self>) -> Self {
-        let callback = link.send_back(Msg::UpdateRoute);
-        let router_agent = RouteAgentBridge::new(callback);
+        #[cfg(feature = "agent")]
+        let router_agent = match &props.route {
+            Some(_) => None,
+            None => {
+                let callback = link.send_back(Msg::UpdateRoute);
+                // The route must be updated by immediately requesting a route update from the service bridge.
+                Some(RouteAgentBridge::with_changes(callback))
+            }
+        };
+        let route = props.route.clone();
 
-        Router {
-            route: Default::default(), // This must be updated by immediately requesting a route update from the service bridge.
+        let mut router = Router {
+            route: Default::default(),
+            switch: None,
             props,
+            #[cfg(feature = "agent")]
             router_agent,
+            link,
+            data: None,
+            pending_route: None,
+            load_generation: 0,
+            load_task: None,
+            kept: ViewCache::new(),
+            direction: None,
+            leaving: None,
+            transition_task: None,
+            timeout_service: TimeoutService::new(),
+            current_first: true,
+        };
+        if let Some(route) = route {
+            router.change_route(route, None);
         }
+        router
     }
 
     fn mounted(&mut self) -> ShouldRender {
-        self.router_agent.send(RouteRequest::GetCurrentRoute);
+        #[cfg(feature = "agent")]
+        {
+            if let Some(router_agent) = &mut self.router_agent {
+                router_agent.send(RouteRequest::GetCurrentRoute);
+            }
+        }
         false
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
         match msg {
-            Msg::UpdateRoute(route) => {
-                let did_change = self.route != route;
-                self.route = route;
-                did_change
+            Msg::UpdateRoute(change) => {
+                let direction = TransitionDirection::from_cause(change.cause);
+                self.change_route(change.current, direction)
+            }
+            Msg::TransitionEnded => {
+                self.transition_task = None;
+                self.leaving.take().is_some()
+            }
+            Msg::Loaded(generation, data) => {
+                if generation != self.load_generation {
+                    // The route changed again before this finished loading.
+                    return false;
+                }
+                self.load_task = None;
+                match self.pending_route.take() {
+                    Some((route, switch)) => self.display(route, switch, Some(data)),
+                    None => self.data = Some(data),
+                }
+                true
             }
             Msg::InnerMessage(m) => {
                 if let Some(cb) = &self.props.callback {
@@ -211,12 +624,78 @@ where
     }
 
     fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        let route = props.route.clone();
+        let mut should_render = !self.props.renders_like(&props);
         self.props = props;
-        true // TODO, this can probably be better now.
+        if self.props.keep_alive.is_none() {
+            self.kept = ViewCache::new();
+        }
+        if let Some(route) = route {
+            should_render |= self.change_route(route, Some(TransitionDirection::Replace));
+        }
+        should_render
     }
 
     fn view(&self) -> VNode<Self> {
-        let switch = SW::switch(self.route.clone());
-        (&self.props.render.0)(switch)
+        let keep_alive = match &self.props.keep_alive {
+            Some(keep_alive) => keep_alive,
+            None if self.props.transition.is_none() => {
+                return self.render_switch(self.switch.as_ref(), self.data.as_ref())
+            }
+            None => {
+                let current = self.render_current();
+                let leaving = match &self.leaving {
+                    Some(leaving) => {
+                        self.render_leaving(leaving, leaving.switch.as_ref(), leaving.data.as_ref())
+                    }
+                    None => html! {},
+                };
+                return if self.current_first {
+                    html! { <>{ current }{ leaving }</> }
+                } else {
+                    html! { <>{ leaving }{ current }</> }
+                };
+            }
+        };
+        let current = self.switch.as_ref().and_then(|switch| {
+            self.kept
+                .find(switch, |a, b| keep_alive.is_same(a, b))
+        });
+        let leaving_index = self.leaving.as_ref().and_then(|leaving| leaving.kept_index);
+        // Kept views are rendered in the order they are kept in,
+        // so their components are reused instead of rebuilt.
+        // The slots of evicted views are rendered empty, so their components are destroyed.
+        let kept = self.kept.iter().enumerate().map(|(index, entry)| {
+            let (switch, data) = match entry {
+                Some(entry) => entry,
+                None => return html! {},
+            };
+            match &self.leaving {
+                _ if Some(index) == current => self.render_current(),
+                Some(leaving) if Some(index) == leaving_index => {
+                    self.render_leaving(leaving, Some(switch), data.as_ref())
+                }
+                _ => html! {
+                    <div hidden=true>{ self.render_switch(Some(switch), data.as_ref()) }</div>
+                },
+            }
+        });
+        let not_kept = match current {
+            Some(_) => html! {},
+            None => self.render_current(),
+        };
+        let leaving_not_kept = match &self.leaving {
+            Some(leaving) if leaving.kept_index.is_none() => {
+                self.render_leaving(leaving, leaving.switch.as_ref(), leaving.data.as_ref())
+            }
+            _ => html! {},
+        };
+        html! {
+            <>
+                { for kept }
+                { not_kept }
+                { leaving_not_kept }
+            </>
+        }
     }
 }
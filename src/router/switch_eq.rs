@@ -0,0 +1,74 @@
+//! Comparison of `Switch` values.
+use std::fmt::{self, Debug, Formatter};
+use std::rc::Rc;
+
+/// Determines if two `Switch` values are the same, for `Switch`es that might not implement
+/// `PartialEq`.
+///
+/// # Example
+/// ```
+///# use yew_router::Switch;
+///# use yew_router::router::SwitchEq;
+/// #[derive(Switch)]
+/// enum S {
+///     #[to = "/users/{id}"]
+///     User { id: usize },
+///     #[to = "/"]
+///     Home,
+/// }
+///
+/// let switch_eq = SwitchEq::by(|a: &S, b: &S| match (a, b) {
+///     (S::User { id: a }, S::User { id: b }) => a == b,
+///     (S::Home, S::Home) => true,
+///     _ => false,
+/// });
+/// assert!(switch_eq.same(&S::User { id: 1 }, &S::User { id: 1 }));
+/// assert!(!switch_eq.same(&S::User { id: 1 }, &S::Home));
+/// ```
+pub struct SwitchEq<SW>(Rc<dyn Fn(&SW, &SW) -> bool>);
+
+impl<SW: PartialEq + 'static> SwitchEq<SW> {
+    /// Compares `Switch` values by their `PartialEq` implementation.
+    pub fn new() -> Self {
+        SwitchEq::by(|a: &SW, b: &SW| a == b)
+    }
+}
+
+impl<SW> SwitchEq<SW> {
+    /// Compares `Switch` values with the function.
+    pub fn by<F: Fn(&SW, &SW) -> bool + 'static>(f: F) -> Self {
+        SwitchEq(Rc::new(f))
+    }
+
+    /// Determines if the `Switch` values are the same.
+    pub fn same(&self, a: &SW, b: &SW) -> bool {
+        (self.0)(a, b)
+    }
+
+    /// Determines if the optional `Switch` values are the same.
+    pub fn same_option(&self, a: Option<&SW>, b: Option<&SW>) -> bool {
+        match (a, b) {
+            (Some(a), Some(b)) => self.same(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<SW: PartialEq + 'static> Default for SwitchEq<SW> {
+    fn default() -> Self {
+        SwitchEq::new()
+    }
+}
+
+impl<SW> Clone for SwitchEq<SW> {
+    fn clone(&self) -> Self {
+        SwitchEq(self.0.clone())
+    }
+}
+
+impl<SW> Debug for SwitchEq<SW> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("SwitchEq").finish()
+    }
+}
@@ -0,0 +1,114 @@
+//! Transitions between the views of routes.
+use crate::route::RouteChangeCause;
+use std::time::Duration;
+
+/// Makes the `Router` keep the view of the previous route mounted for a while after the route
+/// changes, so the change can be animated with CSS.
+///
+/// While the transition lasts, the view of the new route is wrapped in an element with the
+/// `enter_class`, and the view of the previous route is wrapped in an element with the `leave_class`.
+/// Both also have the class of the `TransitionDirection`, so they can slide in opposite directions
+/// when navigating back.
+/// Outside of transitions, the view of the current route is wrapped in an element without classes.
+///
+/// # Example
+/// ```css
+/// .route-enter.route-forward { animation: slide-in-from-right 300ms; }
+/// .route-leave.route-forward { animation: slide-out-to-left 300ms; }
+/// .route-enter.route-back { animation: slide-in-from-left 300ms; }
+/// .route-leave.route-back { animation: slide-out-to-right 300ms; }
+/// .route-leave { position: absolute; top: 0; width: 100%; }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition {
+    /// How long the view of the previous route is kept mounted.
+    pub duration: Duration,
+    /// The class of the element wrapping the view of the new route.
+    pub enter_class: String,
+    /// The class of the element wrapping the view of the previous route.
+    pub leave_class: String,
+}
+
+impl Transition {
+    /// Creates a transition that lasts for the duration,
+    /// using the `route-enter` and `route-leave` classes.
+    pub fn new(duration: Duration) -> Self {
+        Transition {
+            duration,
+            enter_class: "route-enter".to_string(),
+            leave_class: "route-leave".to_string(),
+        }
+    }
+
+    /// Gets the classes of the element wrapping the view of the new route.
+    pub(crate) fn enter_classes(&self, direction: TransitionDirection) -> String {
+        format!("{} {}", self.enter_class, direction.class())
+    }
+
+    /// Gets the classes of the element wrapping the view of the previous route.
+    pub(crate) fn leave_classes(&self, direction: TransitionDirection) -> String {
+        format!("{} {}", self.leave_class, direction.class())
+    }
+}
+
+/// The direction of a transition, determined by what caused the route to change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionDirection {
+    /// The route was changed to a new history entry.
+    Forward,
+    /// The browser navigated back or forward.
+    ///
+    /// Browsers don't tell which, but it is usually back.
+    Back,
+    /// The route was replaced, either in the history or by the `route` prop of the `Router`.
+    Replace,
+}
+
+impl TransitionDirection {
+    /// Gets the direction of the transition for a change with the cause,
+    /// or `None` if there shouldn't be a transition.
+    pub fn from_cause(cause: RouteChangeCause) -> Option<Self> {
+        match cause {
+            RouteChangeCause::Push => Some(TransitionDirection::Forward),
+            RouteChangeCause::Pop => Some(TransitionDirection::Back),
+            RouteChangeCause::Replace => Some(TransitionDirection::Replace),
+            RouteChangeCause::Initial => None,
+        }
+    }
+
+    /// Gets the class that the elements wrapping the views have during a transition.
+    pub fn class(self) -> &'static str {
+        match self {
+            TransitionDirection::Forward => "route-forward",
+            TransitionDirection::Back => "route-back",
+            TransitionDirection::Replace => "route-replace",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn classes() {
+        let transition = Transition::new(Duration::from_millis(300));
+        assert_eq!(
+            transition.enter_classes(TransitionDirection::Forward),
+            "route-enter route-forward"
+        );
+        assert_eq!(
+            transition.leave_classes(TransitionDirection::Back),
+            "route-leave route-back"
+        );
+    }
+
+    #[test]
+    fn initial_routes_are_not_transitioned() {
+        assert_eq!(
+            TransitionDirection::from_cause(RouteChangeCause::Pop),
+            Some(TransitionDirection::Back)
+        );
+        assert_eq!(TransitionDirection::from_cause(RouteChangeCause::Initial), None);
+    }
+}
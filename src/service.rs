@@ -1,17 +1,272 @@
 //! Service to handle routing.
 
-use stdweb::web::event::PopStateEvent;
+use stdweb::web::event::{ClickEvent, IEvent, IMouseEvent, MouseButton, PopStateEvent};
 use stdweb::web::window;
 use stdweb::web::EventListenerHandle;
 use stdweb::web::History;
 use stdweb::web::IEventTarget;
 use stdweb::web::Location;
-use stdweb::Value;
+use stdweb::web::{document, set_timeout, IElement, INonElementParentNode, IParentNode};
+use stdweb::web::Element;
+use stdweb::unstable::TryFrom;
+use stdweb::{js, Value};
 use yew::callback::Callback;
 
-use crate::route::RouteState;
+use crate::config::RouterConfig;
+use crate::matcher::encoding;
+use crate::route::{RouteState, StateError, VersionedState};
+use std::fmt::{self, Debug, Formatter};
 use std::marker::PhantomData;
 
+/// Gets the path that the application is served under.
+///
+/// This is the base path of the installed `RouterConfig`, or the path of the document's
+/// `<base href>` element, without a trailing `/`.
+/// If neither is present, it is empty.
+pub fn base_path() -> String {
+    RouterConfig::with_installed(|config| config.base_path.clone())
+        .unwrap_or_else(base_path_from_document)
+}
+
+fn base_path_from_document() -> String {
+    let href = document()
+        .query_selector("base[href]")
+        .ok()
+        .and_then(|base| base)
+        .and_then(|base| base.get_attribute("href"));
+    match href {
+        Some(href) => {
+            let current_path = window()
+                .location()
+                .and_then(|location| location.pathname().ok())
+                .unwrap_or_default();
+            base_path_from_href(&href, &current_path)
+        }
+        None => String::new(),
+    }
+}
+
+/// Resolves the path of a `<base href>`, which may be relative to the current path.
+fn base_path_from_href(href: &str, current_path: &str) -> String {
+    let href = href.split(|c| c == '?' || c == '#').next().unwrap_or_default();
+    let path = if let Some(scheme_end) = href.find("://") {
+        // Absolute urls include the host, which is followed by the path.
+        let after_scheme = &href[scheme_end + 3..];
+        after_scheme
+            .find('/')
+            .map(|path_start| after_scheme[path_start..].to_string())
+            .unwrap_or_default()
+    } else if href.starts_with('/') {
+        href.to_string()
+    } else {
+        let directory_end = current_path.rfind('/').map_or(0, |index| index + 1);
+        format!("{}{}", &current_path[..directory_end], href)
+    };
+    normalize_base_path(&path)
+}
+
+/// Removes trailing `/`s, so the base path can be put in front of routes that start with `/`.
+pub(crate) fn normalize_base_path(base_path: &str) -> String {
+    let base_path = base_path.trim_end_matches('/');
+    if base_path.is_empty() || base_path.starts_with('/') {
+        base_path.to_string()
+    } else {
+        format!("/{}", base_path)
+    }
+}
+
+/// Removes the base path from the start of the path that the browser is at.
+///
+/// Paths outside of the base path are returned unchanged.
+fn strip_base_path(base_path: &str, path: &str) -> String {
+    if base_path.is_empty() || !path.starts_with(base_path) {
+        return path.to_string();
+    }
+    let rest = &path[base_path.len()..];
+    if rest.is_empty() {
+        "/".to_string()
+    } else if rest.starts_with('/') {
+        rest.to_string()
+    } else {
+        // The path only shares a prefix with the base path, like `/app/v22`.
+        path.to_string()
+    }
+}
+
+/// Gets the path within the base path, or `None` if the path is outside of the base path.
+fn path_within_base_path(base_path: &str, path: &str) -> Option<String> {
+    let stripped = strip_base_path(base_path, path);
+    if base_path.is_empty() || stripped != path {
+        Some(stripped)
+    } else {
+        None
+    }
+}
+
+/// The attribute that stops clicks on a link from being intercepted by `intercept_links`.
+pub const NO_INTERCEPT_ATTRIBUTE: &str = "data-router-ignore";
+
+/// Gets the route of the link that the click should change to,
+/// or `None` if the browser should follow the link itself.
+fn intercepted_route(event: &ClickEvent, base_path: &str) -> Option<String> {
+    let modified = event.ctrl_key() || event.meta_key() || event.shift_key() || event.alt_key();
+    if event.default_prevented() || event.button() != MouseButton::Left || modified {
+        return None;
+    }
+    let link: Element = event
+        .target()
+        .and_then(|target| Element::try_from(target).ok())
+        .and_then(|element| element.closest("a[href]").ok().and_then(|link| link))?;
+    let targets_other_context = link
+        .get_attribute("target")
+        .map_or(false, |target| !target.is_empty() && target != "_self");
+    if targets_other_context
+        || link.has_attribute("download")
+        || link.has_attribute(NO_INTERCEPT_ATTRIBUTE)
+    {
+        return None;
+    }
+    // Links to other origins, and SVG links, which don't have an origin, aren't intercepted.
+    let parts = js! {
+        var link = @{link};
+        if (link.origin !== window.location.origin) {
+            return null;
+        }
+        return [link.pathname, link.search, link.hash];
+    };
+    match Vec::<String>::try_from(parts).ok()?.as_slice() {
+        [path, query, fragment] => {
+            let path = path_within_base_path(base_path, path)?;
+            Some(crate::route::format_route_string(&path, query, fragment))
+        }
+        _ => None,
+    }
+}
+
+/// Puts the base path in front of a route that starts with `/`.
+///
+/// Other routes are relative to the current route, so they are returned unchanged.
+pub(crate) fn prepend_base_path(base_path: &str, route: &str) -> String {
+    if route.starts_with('/') {
+        format!("{}{}", base_path, route)
+    } else {
+        route.to_string()
+    }
+}
+
+/// Gets the id of the element that the fragment, like `#section`, refers to.
+fn fragment_id(fragment: &str) -> Option<String> {
+    let fragment = fragment.trim_start_matches('#');
+    if fragment.is_empty() {
+        None
+    } else {
+        Some(encoding::decode(fragment).unwrap_or_else(|| fragment.to_string()))
+    }
+}
+
+/// Gets the key that the state of the history entry with the id is persisted under.
+fn state_storage_key(entry_id: &str) -> String {
+    format!("yew_router_state:{}", entry_id)
+}
+
+/// The key that the ids of the history entries with persisted states are stored under,
+/// separated by commas, from the least to the most recently persisted.
+const PERSISTED_IDS_KEY: &str = "yew_router_state_ids";
+
+/// The number of history entries whose states are kept in the `sessionStorage`.
+const PERSISTED_STATES: usize = 50;
+
+/// Moves the entry id to the end of the ids, as the most recently persisted one,
+/// and removes the least recently persisted ids that don't fit in the capacity.
+///
+/// Returns the removed ids, whose persisted states should be removed as well.
+fn record_persisted_id(ids: &mut Vec<String>, entry_id: &str, capacity: usize) -> Vec<String> {
+    ids.retain(|id| id != entry_id);
+    ids.push(entry_id.to_string());
+    let excess = ids.len().saturating_sub(capacity);
+    ids.drain(..excess).collect()
+}
+
+/// Gets the version that the route state was stored with by a versioned `RouteService`,
+/// or 0 if it wasn't stored with one.
+///
+/// The version is kept in the envelope described by `unwrap_state`.
+fn state_version(state: &Value) -> u32 {
+    let version = js! {
+        var state = @{state};
+        if (state && state.__yew_router && typeof state.version === "number") {
+            return state.version;
+        }
+        return 0;
+    };
+    u32::try_from(version).unwrap_or(0)
+}
+
+/// The version of states, and how to migrate states stored with older versions.
+struct StateVersioning<T> {
+    version: u32,
+    migrate: fn(u32, Value) -> Result<T, StateError>,
+}
+
+impl<T> Clone for StateVersioning<T> {
+    fn clone(&self) -> Self {
+        StateVersioning {
+            version: self.version,
+            migrate: self.migrate,
+        }
+    }
+}
+
+impl<T> Copy for StateVersioning<T> {}
+
+impl<T> Debug for StateVersioning<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("StateVersioning")
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+/// Converts the route state stored with the version to the current version.
+///
+/// A missing state is converted to the default state.
+fn convert_state<T: RouteState>(
+    version: u32,
+    state: Value,
+    versioning: Option<StateVersioning<T>>,
+) -> Result<T, StateError> {
+    if let Value::Null | Value::Undefined = state {
+        return Ok(T::default());
+    }
+    let current_version = versioning.map_or(0, |versioning| versioning.version);
+    match versioning {
+        _ if version == current_version => {
+            T::try_from(state).map_err(|_| StateError::Invalid { version })
+        }
+        Some(versioning) if version < current_version => (versioning.migrate)(version, state),
+        _ => Err(StateError::UnknownVersion(version)),
+    }
+}
+
+/// Removes the envelope that route services wrap around the route state in the History API.
+///
+/// The envelope is an object that is marked by `__yew_router: true`, and has these fields:
+/// * `state` - The route state.
+/// * `x` and `y` - The scroll position, saved by `save_scroll_position`.
+/// * `id` - The id of the history entry, generated by `entry_id`.
+/// * `version` - The version of the route state, if states are versioned.
+///
+/// The fields besides `state` are only present once they are set.
+fn unwrap_state(state: Value) -> Value {
+    js! {
+        var state = @{state};
+        if (state && state.__yew_router) {
+            return state.state;
+        }
+        return state;
+    }
+}
+
 /// A service that facilitates manipulation of the browser's URL bar and responding to browser
 /// 'forward' and 'back' events.
 ///
@@ -20,7 +275,11 @@ use std::marker::PhantomData;
 pub struct RouteService<T> {
     history: History,
     location: Location,
+    base_path: String,
     event_listener: Option<EventListenerHandle>, // maybe this should not be stored in the service itself, and instead returned by register_callback()
+    link_listener: Option<EventListenerHandle>,
+    state_versioning: Option<StateVersioning<T>>,
+    state_error_callback: Option<Callback<StateError>>,
     phantom_data: PhantomData<T>,
 }
 
@@ -35,34 +294,53 @@ where
 
 impl<T> RouteService<T> {
     /// Creates the route service.
+    ///
+    /// It uses the base path provided by `base_path()`.
     pub fn new() -> RouteService<T> {
+        Self::with_base_path(&base_path())
+    }
+
+    /// Creates the route service for an application served under the base path, like `/app/v2`.
+    ///
+    /// The base path is stripped from routes gotten from the browser,
+    /// and prepended to routes that start with `/` when they are set.
+    pub fn with_base_path(base_path: &str) -> RouteService<T> {
         let location = window()
             .location()
             .expect("browser does not support location API");
         RouteService {
             history: window().history(),
             location,
+            base_path: normalize_base_path(base_path),
             event_listener: None,
+            link_listener: None,
+            state_versioning: None,
+            state_error_callback: None,
             phantom_data: PhantomData,
         }
     }
 
     #[inline]
-    fn get_route_from_location(location: &Location) -> String {
-        let path = location.pathname().unwrap();
+    fn get_route_from_location(location: &Location, base_path: &str) -> String {
+        let path = strip_base_path(base_path, &location.pathname().unwrap());
         let query = location.search().unwrap();
         let fragment = location.hash().unwrap();
         crate::route::format_route_string(&path, &query, &fragment)
     }
 
+    /// Gets the path that the application is served under.
+    pub fn base_path(&self) -> &str {
+        &self.base_path
+    }
+
     /// Gets the concatenated path, query, and fragment strings
     pub fn get_route(&self) -> String {
-        Self::get_route_from_location(&self.location)
+        Self::get_route_from_location(&self.location, &self.base_path)
     }
 
-    /// Gets the path name of the current url.
+    /// Gets the path name of the current url, without the base path.
     pub fn get_path(&self) -> String {
-        self.location.pathname().unwrap()
+        strip_base_path(&self.base_path, &self.location.pathname().unwrap())
     }
 
     /// Gets the query string of the current url.
@@ -80,33 +358,378 @@ impl<T> RouteService<T>
 where
     T: RouteState,
 {
+    /// Stores states with the version of `T`,
+    /// and migrates states that were stored with older versions when they are popped.
+    ///
+    /// This should be called before `register_callback`.
+    pub fn set_state_versioning(&mut self)
+    where
+        T: VersionedState,
+    {
+        self.state_versioning = Some(StateVersioning {
+            version: T::VERSION,
+            migrate: T::migrate,
+        });
+    }
+
+    /// Sets the callback that is called when a popped state can't be converted to the route state.
+    ///
+    /// The route is still changed, with the default state.
+    /// This should be called before `register_callback`.
+    pub fn set_state_error_callback(&mut self, callback: Callback<StateError>) {
+        self.state_error_callback = Some(callback);
+    }
+
     /// Registers a callback to the route service.
     /// Callbacks will be called when the History API experiences a change such as
     /// popping a state off of its stack when the forward or back buttons are pressed.
     pub fn register_callback(&mut self, callback: Callback<(String, T)>) {
+        let base_path = self.base_path.clone();
+        let state_versioning = self.state_versioning;
+        let state_error_callback = self.state_error_callback.clone();
         self.event_listener = Some(window().add_event_listener(move |event: PopStateEvent| {
             let state_value: Value = event.state();
-            let state: T = T::try_from(state_value).unwrap_or_default();
+            let version = state_version(&state_value);
+            let state_value = unwrap_state(state_value);
+            let state: T = convert_state(version, state_value, state_versioning)
+                .unwrap_or_else(|error| {
+                    if let Some(state_error_callback) = &state_error_callback {
+                        state_error_callback.emit(error)
+                    }
+                    T::default()
+                });
 
             // Can't use the existing location, because this is a callback, and can't move it in here.
             let location: Location = window().location().unwrap();
-            let route: String = Self::get_route_from_location(&location);
+            let route: String = Self::get_route_from_location(&location, &base_path);
 
             callback.emit((route.clone(), state))
         }));
     }
 
+    /// Intercepts clicks on links to routes within the base path,
+    /// and calls the callback with their routes instead of letting the browser load them.
+    ///
+    /// Clicks with modifier keys, or on links with a `target` other than `_self`,
+    /// a `download` attribute, or the `data-router-ignore` attribute, aren't intercepted.
+    pub fn intercept_links(&mut self, callback: Callback<String>) {
+        let base_path = self.base_path.clone();
+        self.link_listener = Some(document().add_event_listener(move |event: ClickEvent| {
+            if let Some(route) = intercepted_route(&event, &base_path) {
+                event.prevent_default();
+                callback.emit(route)
+            }
+        }));
+    }
+
     /// Sets the browser's url bar to contain the provided route,
     /// and creates a history entry that can be navigated via the forward and back buttons.
     /// The route should be a relative path that starts with a '/'.
+    /// The base path will be prepended to it.
     /// A state object be stored with the url.
     pub fn set_route(&mut self, route: &str, state: T) {
-        self.history.push_state(state, "", Some(route));
+        self.set_route_with_title(route, state, "")
+    }
+
+    /// Sets the route like `set_route`, and sets the title of the document and the history entry.
+    ///
+    /// An empty title leaves the document's title unchanged.
+    pub fn set_route_with_title(&mut self, route: &str, state: T, title: &str) {
+        let route = prepend_base_path(&self.base_path, route);
+        let state = self.versioned_state(state);
+        self.history.push_state(state, title, Some(&route));
+        self.set_title(title);
     }
 
     /// Replaces the route with another one removing the most recent history event and
     /// creating another history event in its place.
     pub fn replace_route(&mut self, route: &str, state: T) {
-        let _ = self.history.replace_state(state, "", Some(route));
+        self.replace_route_with_title(route, state, "")
+    }
+
+    /// Replaces the route like `replace_route`, and sets the title of the document and the
+    /// history entry.
+    ///
+    /// An empty title leaves the document's title unchanged.
+    pub fn replace_route_with_title(&mut self, route: &str, state: T, title: &str) {
+        let route = prepend_base_path(&self.base_path, route);
+        let state = self.versioned_state(state);
+        let _ = self.history.replace_state(state, title, Some(&route));
+        self.set_title(title);
+    }
+
+    /// Wraps the state in the envelope described by `unwrap_state`, with its version,
+    /// if states are versioned.
+    fn versioned_state(&self, state: T) -> Value {
+        match &self.state_versioning {
+            Some(versioning) => {
+                let version = versioning.version;
+                js! {
+                    return { __yew_router: true, state: @{state}, version: @{version} };
+                }
+            }
+            None => js! { return @{state}; },
+        }
+    }
+
+    /// Sets the title of the document, which browsers show for the current history entry.
+    ///
+    /// An empty title leaves the document's title unchanged.
+    pub fn set_title(&self, title: &str) {
+        if !title.is_empty() {
+            document().set_title(title);
+        }
+    }
+
+    /// Stops the browser from restoring the scroll position itself when navigating back or forward,
+    /// so it doesn't interfere with `scroll_to`.
+    pub fn set_manual_scroll_restoration(&self) {
+        js! { @(no_return)
+            if ("scrollRestoration" in history) {
+                history.scrollRestoration = "manual";
+            }
+        }
+    }
+
+    /// Saves the page's scroll position in the state of the current history entry.
+    ///
+    /// The route state is kept alongside it,
+    /// and the scroll position can be gotten from `scroll_position` after navigating back to the entry.
+    pub fn save_scroll_position(&mut self) {
+        js! { @(no_return)
+            var state = history.state;
+            if (!(state && state.__yew_router)) {
+                state = { __yew_router: true, state: state };
+            }
+            state.x = window.pageXOffset;
+            state.y = window.pageYOffset;
+            history.replaceState(state, "");
+        }
+    }
+
+    /// Gets the id of the current history entry, which is unique within the browser tab.
+    ///
+    /// If the entry doesn't have an id yet, one is generated and saved in its state,
+    /// alongside the route state.
+    pub fn entry_id(&mut self) -> String {
+        let id = js! {
+            var state = history.state;
+            if (!(state && state.__yew_router)) {
+                state = { __yew_router: true, state: state };
+            }
+            if (!state.id) {
+                state.id = Date.now().toString(36) + Math.random().toString(36).slice(2);
+                history.replaceState(state, "");
+            }
+            return state.id;
+        };
+        String::try_from(id).unwrap_or_default()
+    }
+
+    /// Persists the serialized state of the current history entry in the `sessionStorage`,
+    /// so it survives reloading the page.
+    ///
+    /// Only the states of the most recently persisted entries are kept,
+    /// so the storage doesn't grow for as long as the tab is open.
+    ///
+    /// `None` removes the persisted state.
+    pub fn persist_state(&mut self, serialized_state: Option<&str>) {
+        let entry_id = self.entry_id();
+        let key = state_storage_key(&entry_id);
+        let storage = window().session_storage();
+        let mut ids: Vec<String> = storage
+            .get(PERSISTED_IDS_KEY)
+            .unwrap_or_default()
+            .split(',')
+            .filter(|id| !id.is_empty())
+            .map(String::from)
+            .collect();
+        match serialized_state {
+            Some(serialized_state) => {
+                for removed_id in record_persisted_id(&mut ids, &entry_id, PERSISTED_STATES) {
+                    storage.remove(&state_storage_key(&removed_id));
+                }
+                // Storing fails if the storage is full, in which case the state isn't persisted.
+                let _ = storage.insert(&key, serialized_state);
+            }
+            None => {
+                ids.retain(|id| *id != entry_id);
+                storage.remove(&key);
+            }
+        }
+        let _ = storage.insert(PERSISTED_IDS_KEY, &ids.join(","));
+    }
+
+    /// Gets the serialized state of the current history entry that was persisted by `persist_state`.
+    pub fn persisted_state(&mut self) -> Option<String> {
+        let key = state_storage_key(&self.entry_id());
+        window().session_storage().get(&key)
+    }
+
+    /// Gets the scroll position saved in the state of the current history entry.
+    pub fn scroll_position(&self) -> Option<(f64, f64)> {
+        let position = js! {
+            var state = history.state;
+            if (state && state.__yew_router) {
+                return [state.x, state.y];
+            }
+            return null;
+        };
+        match Vec::<f64>::try_from(position).ok()?.as_slice() {
+            [x, y] => Some((*x, *y)),
+            _ => None,
+        }
+    }
+
+    /// Scrolls the page to the position.
+    ///
+    /// This happens after the components that respond to the route change have rendered.
+    pub fn scroll_to(&self, x: f64, y: f64) {
+        set_timeout(
+            move || {
+                js! { @(no_return)
+                    window.scrollTo(@{x}, @{y});
+                }
+            },
+            0,
+        );
+    }
+
+    /// Scrolls the element whose id matches the fragment of the current url into view.
+    ///
+    /// This happens after the components that respond to the route change have rendered.
+    /// Returns false if the url doesn't have a fragment.
+    pub fn scroll_to_fragment(&self) -> bool {
+        match fragment_id(&self.get_fragment()) {
+            Some(id) => {
+                set_timeout(
+                    move || {
+                        if let Some(element) = document().get_element_by_id(&id) {
+                            js! { @(no_return)
+                                @{element}.scrollIntoView();
+                            }
+                        }
+                    },
+                    0,
+                );
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn base_path_is_normalized() {
+        assert_eq!(normalize_base_path("/app/v2/"), "/app/v2");
+        assert_eq!(normalize_base_path("app/v2"), "/app/v2");
+        assert_eq!(normalize_base_path("/"), "");
+        assert_eq!(normalize_base_path(""), "");
+    }
+
+    #[test]
+    fn base_href_is_resolved() {
+        assert_eq!(base_path_from_href("/app/v2/", "/"), "/app/v2");
+        assert_eq!(
+            base_path_from_href("https://example.com/app/v2/", "/"),
+            "/app/v2"
+        );
+        assert_eq!(base_path_from_href("https://example.com", "/"), "");
+        assert_eq!(base_path_from_href("v2/", "/app/index.html"), "/app/v2");
+        assert_eq!(base_path_from_href("/app/?lorem#ipsum", "/"), "/app");
+    }
+
+    #[test]
+    fn state_storage_keys_are_namespaced() {
+        assert_eq!(state_storage_key("k2x9"), "yew_router_state:k2x9");
+    }
+
+    #[test]
+    fn least_recently_persisted_ids_are_pruned() {
+        let mut ids = Vec::new();
+        assert!(record_persisted_id(&mut ids, "lorem", 2).is_empty());
+        assert!(record_persisted_id(&mut ids, "ipsum", 2).is_empty());
+        assert!(record_persisted_id(&mut ids, "lorem", 2).is_empty());
+        assert_eq!(ids, vec!["ipsum", "lorem"]);
+        assert_eq!(record_persisted_id(&mut ids, "dolor", 2), vec!["ipsum"]);
+        assert_eq!(ids, vec!["lorem", "dolor"]);
+    }
+
+    fn versioning() -> Option<StateVersioning<String>> {
+        fn migrate(version: u32, state: Value) -> Result<String, StateError> {
+            String::try_from(state)
+                .map(|state| format!("{} from version {}", state, version))
+                .map_err(|_| StateError::Invalid { version })
+        }
+        Some(StateVersioning { version: 2, migrate })
+    }
+
+    #[test]
+    fn current_states_are_converted() {
+        let state = convert_state(2, Value::from("lorem"), versioning());
+        assert_eq!(state, Ok("lorem".to_string()));
+        let state = convert_state(2, Value::from(12), versioning());
+        assert_eq!(state, Err(StateError::Invalid { version: 2 }));
+    }
+
+    #[test]
+    fn old_states_are_migrated() {
+        let state = convert_state(0, Value::from("lorem"), versioning());
+        assert_eq!(state, Ok("lorem from version 0".to_string()));
+    }
+
+    #[test]
+    fn new_states_are_unknown() {
+        let state = convert_state(3, Value::from("lorem"), versioning());
+        assert_eq!(state, Err(StateError::UnknownVersion(3)));
+        let state = convert_state::<String>(1, Value::from("lorem"), None);
+        assert_eq!(state, Err(StateError::UnknownVersion(1)));
+    }
+
+    #[test]
+    fn missing_states_are_default() {
+        let state = convert_state(1, Value::Null, versioning());
+        assert_eq!(state, Ok(String::new()));
+    }
+
+    #[test]
+    fn paths_outside_of_the_base_path_are_excluded() {
+        assert_eq!(
+            path_within_base_path("/app", "/app/users"),
+            Some("/users".to_string())
+        );
+        assert_eq!(path_within_base_path("/app", "/app"), Some("/".to_string()));
+        assert_eq!(path_within_base_path("/app", "/other"), None);
+        assert_eq!(path_within_base_path("", "/other"), Some("/other".to_string()));
+    }
+
+    #[test]
+    fn strip() {
+        assert_eq!(strip_base_path("/app/v2", "/app/v2/users"), "/users");
+        assert_eq!(strip_base_path("/app/v2", "/app/v2"), "/");
+        assert_eq!(strip_base_path("/app/v2", "/app/v22/users"), "/app/v22/users");
+        assert_eq!(strip_base_path("/app/v2", "/other"), "/other");
+        assert_eq!(strip_base_path("", "/users"), "/users");
+    }
+
+    #[test]
+    fn fragment() {
+        assert_eq!(fragment_id("#section"), Some("section".to_string()));
+        assert_eq!(fragment_id("#lorem%20ipsum"), Some("lorem ipsum".to_string()));
+        assert_eq!(fragment_id("section"), Some("section".to_string()));
+        assert_eq!(fragment_id("#"), None);
+        assert_eq!(fragment_id(""), None);
+    }
+
+    #[test]
+    fn prepend() {
+        assert_eq!(prepend_base_path("/app/v2", "/users"), "/app/v2/users");
+        assert_eq!(prepend_base_path("/app/v2", "/"), "/app/v2/");
+        assert_eq!(prepend_base_path("/app/v2", "?lorem"), "?lorem");
+        assert_eq!(prepend_base_path("", "/users"), "/users");
     }
 }
@@ -1,6 +1,8 @@
 //! Route based on enums.
+use crate::matcher::{MatchReport, RouteMatcher};
 use crate::route::Route;
 use crate::RouteState;
+use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 
 /// Routing trait for enums
@@ -27,8 +29,32 @@ use std::str::FromStr;
 /// assert_eq!(TestEnum::switch(Route::<()>::from("/capture/string/lorem")), Some(TestEnum::CaptureString{path: "lorem".to_string()}));
 /// assert_eq!(TestEnum::switch(Route::<()>::from("/capture/number/22")), Some(TestEnum::CaptureNumber{num: 22}));
 /// assert_eq!(TestEnum::switch(Route::<()>::from("/capture/unnamed/lorem")), Some(TestEnum::CaptureUnnamed("lorem".to_string())));
+///
+/// // Find out why a route didn't produce the expected variant.
+/// let reports = TestEnum::explain(Route::<()>::from("/capture/number/lorem"));
+/// let report = reports.iter().find(|r| r.variant == "CaptureNumber").unwrap();
+/// assert!(report.report.matched);
+/// assert!(!report.produced); // "lorem" isn't a number
+/// ```
+///
+/// Variants are tried in the order they are declared in.
+/// With the `#[most_specific]` attribute, the variant with the most specific matcher is tried first,
+/// so the order of the variants doesn't matter.
 /// ```
+/// use yew_router::Switch;
+/// use yew_router::route::Route;
+/// #[derive(Debug, Switch, PartialEq)]
+/// #[most_specific]
+/// enum UserRoute {
+///     #[to = "/users/{id}"]
+///     User{id: usize},
+///     #[to = "/users/new"]
+///     NewUser,
+/// }
 ///
+/// assert_eq!(UserRoute::switch(Route::<()>::from("/users/new")), Some(UserRoute::NewUser));
+/// assert_eq!(UserRoute::switch(Route::<()>::from("/users/12")), Some(UserRoute::User{id: 12}));
+/// ```
 pub trait Switch: Sized {
     /// Based on a route, possibly produce an itself.
     fn switch<T: RouteState>(route: Route<T>) -> Option<Self>;
@@ -38,6 +64,86 @@ pub trait Switch: Sized {
     fn key_not_available() -> Option<Self> {
         None
     }
+
+    /// Explains how the route was matched against each of the matchers that can produce this.
+    ///
+    /// Derived implementations produce a report for every variant, in the order they are tried.
+    /// Other implementations don't use matchers, and produce no reports.
+    fn explain<T: RouteState>(_route: Route<T>) -> Vec<SwitchReport> {
+        Vec::new()
+    }
+
+    /// Describes the matchers that can produce this, without needing a route.
+    ///
+    /// Derived implementations produce a description for every variant, in the order they are tried.
+    /// Other implementations don't use matchers, and produce no descriptions.
+    ///
+    /// A [RouteTable](route_table/struct.RouteTable.html) can be built from the descriptions
+    /// to list every route, or to generate a sitemap.
+    fn describe() -> Vec<SwitchDescription> {
+        Vec::new()
+    }
+
+    /// Gets the title of the page for this route.
+    ///
+    /// Derived implementations format the fields into the template of the `#[title]` attribute.
+    /// ```
+    ///# use yew_router::Switch;
+    /// #[derive(Switch)]
+    /// enum AppRoute {
+    ///     #[to = "/users/{id}"]
+    ///     #[title = "Profile {id}"]
+    ///     Profile { id: usize },
+    ///     #[to = "/"]
+    ///     Home,
+    /// }
+    ///
+    /// assert_eq!(AppRoute::Profile { id: 12 }.title(), Some("Profile 12".to_string()));
+    /// assert_eq!(AppRoute::Home.title(), None);
+    /// ```
+    fn title(&self) -> Option<String> {
+        None
+    }
+}
+
+/// A description of a single variant of a `Switch`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwitchDescription {
+    /// The name of the variant, or of the struct if the `Switch` was derived for a struct.
+    pub variant: &'static str,
+    /// The matcher that the variant is matched with.
+    pub matcher: RouteMatcher,
+    /// The names of the captures of the matcher, sorted alphabetically.
+    pub capture_names: Vec<String>,
+    /// The name of the capture, like `{*:rest}`, that the children are matched against.
+    pub rest: Option<String>,
+    /// The descriptions of the `Switch` that is produced from the `rest` capture.
+    pub children: Vec<SwitchDescription>,
+}
+
+/// A report of how a route was matched against a single variant of a `Switch`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwitchReport {
+    /// The name of the variant, or of the struct if the `Switch` was derived for a struct.
+    pub variant: &'static str,
+    /// How the variant's matcher attempted to match the route.
+    pub report: MatchReport,
+    /// Whether the variant would be produced from the route.
+    ///
+    /// Even if the matcher matched, the variant won't be produced if a captured value
+    /// couldn't be converted into the type of its field.
+    pub produced: bool,
+}
+
+impl Display for SwitchReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let produced = if self.produced {
+            "produced"
+        } else {
+            "not produced"
+        };
+        write!(f, "{} ({}): {}", self.variant, produced, self.report)
+    }
 }
 
 impl<U: Switch> Switch for Option<U> {
@@ -49,6 +155,14 @@ impl<U: Switch> Switch for Option<U> {
     fn key_not_available() -> Option<Self> {
         Some(None)
     }
+
+    fn describe() -> Vec<SwitchDescription> {
+        U::describe()
+    }
+
+    fn title(&self) -> Option<String> {
+        self.as_ref().and_then(Switch::title)
+    }
 }
 
 impl<U, E> Switch for Result<U, E>